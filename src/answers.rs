@@ -0,0 +1,115 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+
+use crate::types::{AdventError, AdventResult, Answer, Part};
+
+/// The file in each day's input directory that holds the expected answers
+pub const ANSWERS_FILE: &str = "answers.toml";
+
+/// The expected answers for the input files in one directory.
+///
+/// The file has a table for each input file, with the answers for
+/// parts a and b.  Either part can be left out if its answer isn't known.
+///
+/// ```toml
+/// ["sample.txt"]
+/// a = 7
+/// b = "JGAJEFKU"
+/// ```
+#[derive(Debug, Default)]
+pub struct Answers {
+    by_file: BTreeMap<String, HashMap<Part, Answer>>,
+}
+
+impl Answers {
+    /// Reads the answers file in the given directory.  If there is no
+    /// answers file, none of the answers are known.
+    pub fn load(dir: &str) -> AdventResult<Answers> {
+        let path = Path::new(dir).join(ANSWERS_FILE);
+        match fs::read_to_string(&path) {
+            Ok(text) => Answers::parse(&text)
+                .map_err(|e| AdventError::new(&format!("{}: {}", path.display(), e)).into()),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Answers::default()),
+            Err(e) => Err(Box::new(e)),
+        }
+    }
+
+    pub fn parse(text: &str) -> AdventResult<Answers> {
+        let table: toml::Table = text.parse()?;
+        let mut by_file = BTreeMap::new();
+        for (file_name, value) in table {
+            let parts = value
+                .as_table()
+                .ok_or_else(|| AdventError::new(&format!("expected a table for {}", file_name)))?;
+            let mut answers = HashMap::new();
+            for (key, value) in parts {
+                let part: Part = key.parse()?;
+                answers.insert(part, parse_answer(value)?);
+            }
+            by_file.insert(file_name, answers);
+        }
+        Ok(Answers { by_file })
+    }
+
+    /// The answer expected for one part, on one of the input files
+    pub fn expected(&self, file_name: &str, part: Part) -> Option<&Answer> {
+        self.by_file.get(file_name)?.get(&part)
+    }
+
+    /// All of the input files that have answers, in alphabetical order
+    pub fn file_names(&self) -> impl Iterator<Item = &str> {
+        self.by_file.keys().map(|s| &s[..])
+    }
+}
+
+/// Answers are whole numbers or strings.  Numbers too big for TOML can be
+/// written as strings of digits.
+fn parse_answer(value: &toml::Value) -> AdventResult<Answer> {
+    match value {
+        toml::Value::Integer(n) if 0 <= *n => Ok(Answer::from(*n as u64)),
+        toml::Value::String(s) => Ok(s.parse()?),
+        _ => Err(Box::new(AdventError::new(&format!(
+            "not a valid answer: {}",
+            value
+        )))),
+    }
+}
+
+#[test]
+fn test_parse_answers() {
+    let answers = Answers::parse(
+        r#"
+        ["sample.txt"]
+        a = 7
+        b = "123456789012345678901234567890"
+
+        ["input.txt"]
+        b = "JGAJEFKU"
+        "#,
+    )
+    .unwrap();
+    assert_eq!(
+        Some(&Answer::from(7u64)),
+        answers.expected("sample.txt", Part::A)
+    );
+    assert_eq!(
+        "123456789012345678901234567890",
+        answers.expected("sample.txt", Part::B).unwrap().to_string()
+    );
+    assert_eq!(None, answers.expected("input.txt", Part::A));
+    assert_eq!(
+        Some(&Answer::from("JGAJEFKU")),
+        answers.expected("input.txt", Part::B)
+    );
+    assert_eq!(None, answers.expected("other.txt", Part::A));
+    assert_eq!(
+        vec!["input.txt", "sample.txt"],
+        answers.file_names().collect::<Vec<_>>()
+    );
+
+    assert!(Answers::parse("[\"sample.txt\"]\nc = 1").is_err());
+    assert!(Answers::parse("[\"sample.txt\"]\na = -1").is_err());
+    assert!(Answers::parse("a = 1").is_err());
+}
@@ -0,0 +1,147 @@
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use crate::input::Input;
+use crate::types::{day_name, AdventResult, Day, Part};
+use crate::util::{format_duration, print_table};
+use crate::verify::INPUT_FILES;
+
+/// The spread of times measured for something that was run several times
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Timings {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Timings {
+    /// Summarizes a non-empty collection of measurements
+    pub fn from_samples(samples: &[Duration]) -> Timings {
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        Timings {
+            min: sorted[0],
+            median: sorted[sorted.len() / 2],
+            max: *sorted.last().unwrap(),
+        }
+    }
+}
+
+#[test]
+fn test_timings_from_samples() {
+    let ms = Duration::from_millis;
+    assert_eq!(
+        Timings {
+            min: ms(1),
+            median: ms(4),
+            max: ms(9)
+        },
+        Timings::from_samples(&[ms(9), ms(1), ms(4)])
+    );
+}
+
+/// How long one part of one day took on one input file.
+///
+/// The solvers do their own parsing, so the time to read the file and split
+/// it into lines is the only part of the parsing we can measure separately.
+/// Everything else is in the solve time.  Solvers that stream do all of
+/// their reading while they solve, so for them, loading is just opening
+/// the file.
+pub struct BenchResult {
+    pub year: usize,
+    pub day: usize,
+    pub part: Part,
+    pub input: String,
+    pub load: Timings,
+    pub solve: Timings,
+}
+
+/// Runs one part of a day on one input file the given number of times.
+pub fn bench_one(day: &Day, part: Part, path: &str, runs: usize) -> AdventResult<BenchResult> {
+    let day_part = day.part(part);
+    let input = Input::File(PathBuf::from(path));
+    let mut load_times = Vec::new();
+    let mut solve_times = Vec::new();
+    for _ in 0..runs {
+        let start = Instant::now();
+        let loaded = day_part.load(&input)?;
+        load_times.push(start.elapsed());
+
+        let start = Instant::now();
+        day_part.solve(loaded)?;
+        solve_times.push(start.elapsed());
+    }
+    Ok(BenchResult {
+        year: day.year,
+        day: day.number,
+        part,
+        input: path.to_string(),
+        load: Timings::from_samples(&load_times),
+        solve: Timings::from_samples(&solve_times),
+    })
+}
+
+/// Benchmarks the given parts of days, either on the given input file, or
+/// on the usual input files.
+pub fn bench_days(
+    problems: &[(&Day, Part)],
+    input: Option<&str>,
+    runs: usize,
+) -> AdventResult<Vec<BenchResult>> {
+    let mut results = Vec::new();
+    for &(day, part) in problems {
+        let paths: Vec<String> = match input {
+            Some(path) => vec![path.to_string()],
+            None => INPUT_FILES
+                .iter()
+                .map(|file_name| format!("{}/{}", day.input_dir(), file_name))
+                .collect(),
+        };
+        for path in paths.iter() {
+            let result = bench_one(day, part, path, runs)?;
+            println!(
+                "{} part {}: {} -> {}",
+                day,
+                part,
+                path,
+                format_duration(result.solve.median)
+            );
+            results.push(result);
+        }
+    }
+    Ok(results)
+}
+
+/// Prints a table of the min/median/max times for everything benchmarked.
+pub fn print_bench_summary(results: &[BenchResult], runs: usize) {
+    let header = [
+        "day",
+        "part",
+        "input",
+        "load min",
+        "load median",
+        "load max",
+        "solve min",
+        "solve median",
+        "solve max",
+    ];
+    let mut rows = vec![header.iter().map(|s| s.to_string()).collect()];
+    for r in results {
+        rows.push(vec![
+            day_name(r.year, r.day),
+            r.part.to_string(),
+            r.input.clone(),
+            format_duration(r.load.min),
+            format_duration(r.load.median),
+            format_duration(r.load.max),
+            format_duration(r.solve.min),
+            format_duration(r.solve.median),
+            format_duration(r.solve.max),
+        ]);
+    }
+
+    println!("\n########");
+    println!("# Benchmark ({} runs each)", runs);
+    println!("########\n");
+    print_table(&rows);
+}
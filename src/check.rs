@@ -0,0 +1,58 @@
+use crate::input::Input;
+use crate::types::{AdventError, AdventResult, Day};
+
+/// Checks an input for anything a day's solvers don't expect, and returns
+/// all of the problems found, in the order of the lines they're on.  None
+/// means the day doesn't know how to check its input.
+pub fn check_input(day: &Day, input: &Input) -> AdventResult<Option<Vec<AdventError>>> {
+    let validate = match day.validate {
+        Some(validate) => validate,
+        None => return Ok(None),
+    };
+    let lines = input.lines()?;
+    let lines: Vec<&str> = lines.iter().map(|s| &s[..]).collect();
+    let mut problems = validate(&lines);
+    problems.sort_by_key(|e| e.location().map(|location| location.line));
+    Ok(Some(match input {
+        Input::File(path) => {
+            let file = path.display().to_string();
+            problems.into_iter().map(|e| e.in_file(&file)).collect()
+        }
+        Input::Bytes(_) => problems,
+    }))
+}
+
+#[test]
+fn test_check_input() {
+    use crate::types::DayPart;
+
+    fn solve(_lines: &[&str]) -> AdventResult<crate::types::Answer> {
+        Ok(0u64.into())
+    }
+
+    fn no_blank_lines(lines: &[&str]) -> Vec<AdventError> {
+        lines
+            .iter()
+            .enumerate()
+            .filter(|(_, line)| line.is_empty())
+            .map(|(i, line)| AdventError::parse(i + 1, line, "blank line"))
+            .collect()
+    }
+
+    let day = Day::new(2021, 1, DayPart::new(solve), DayPart::new(solve));
+    assert!(check_input(&day, &Input::text("")).unwrap().is_none());
+
+    let day = day.with_validator(no_blank_lines);
+    let problems = check_input(&day, &Input::text("a\n\nb\n\n"))
+        .unwrap()
+        .unwrap();
+    let lines: Vec<usize> = problems
+        .iter()
+        .map(|e| e.location().unwrap().line)
+        .collect();
+    assert_eq!(vec![2, 4], lines);
+    assert!(check_input(&day, &Input::text("a\nb\n"))
+        .unwrap()
+        .unwrap()
+        .is_empty());
+}
@@ -0,0 +1,559 @@
+use std::str::FromStr;
+use std::time::Duration;
+
+#[cfg(test)]
+use crate::selector::available_2021;
+use crate::selector::{parse_day, parse_selector, Selection};
+use crate::types::{AdventError, AdventResult, Part};
+
+pub const USAGE: &str =
+    "Usage: advent <days>|all [--part a|b] [--input <file>|-] [--format text|json]
+                         [--jobs <n>] [--timeout <seconds>]
+       advent bench [<days>|all] [--part a|b] [--input <file>] [--runs <n>]
+       advent watch <days> [--part a|b] [--timeout <seconds>]
+       advent new <day> [--year <year>]
+       advent history <days> [--part a|b] [--threshold <percent>]
+       advent check <day> <file>|- [--part a|b] [--timeout <seconds>]
+       advent gen <day> [--seed <n>] [--size <n>]
+       advent diff [<days>|all] [--part a|b] [--seed <n>] [--runs <n>] [--size <n>]
+                   [--timeout <seconds>]
+
+<days> is a list of days or ranges of days, each of which can end with a
+part: 3-7, 1,5,19b, 24a.  Days are in the latest year unless they start with
+one, like 2021/24.  A year by itself, like 2021, is all of its days.
+
+--timeout is how long each part gets for each input.  The default is 60
+seconds; 0 means no limit.
+
+Every run is added to history.jsonl.  history shows how the answers and times
+changed, and flags runs more than --threshold percent slower than usual (the
+default is 25).
+
+check looks for everything in an input that the day's solvers don't expect,
+and reports all of it.  If nothing is wrong, the input is solved.
+
+gen prints a random input for a day.  The same seed and size always make the
+same input.  What the size means depends on the day, like the number of
+lines, or the width of a grid.
+
+diff checks days' solvers against their simple, slow reference solvers, on
+--runs random inputs (20 by default), starting with --seed.  The inputs are
+small, unless --size says otherwise.  Each input they disagree on is
+reported, with the gen command that makes it.";
+
+/// The first year there were puzzles
+pub const FIRST_YEAR: usize = 2015;
+
+/// How long each solver gets, unless the command line says otherwise
+pub const DEFAULT_TIME_LIMIT: Duration = Duration::from_secs(60);
+
+/// How much slower than usual a run has to be to count as a regression,
+/// unless the command line says otherwise
+pub const DEFAULT_THRESHOLD: f64 = 0.25;
+
+/// The things the program knows how to do
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Command {
+    // Check the answers, or solve a given input
+    Run,
+
+    // Time how long each part takes
+    Bench,
+
+    // Make the files for a new day
+    New,
+
+    // Check the answers again whenever the input files change
+    Watch,
+
+    // Show the results of earlier runs
+    History,
+
+    // Check that an input is what the solvers expect, and then solve it
+    Check,
+
+    // Make a random input
+    Gen,
+
+    // Check solvers against reference solvers, on random inputs
+    Diff,
+}
+
+/// How the results of a run are printed
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = AdventError;
+
+    fn from_str(s: &str) -> Result<Format, AdventError> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(AdventError::new(&format!("unknown format: {}", s))),
+        }
+    }
+}
+
+/// What the command line asked us to do
+#[derive(Debug, PartialEq)]
+pub struct Options {
+    pub command: Command,
+
+    // The parts of the days to run
+    pub selection: Vec<Selection>,
+
+    // An input file to solve instead of the checked-in ones.  "-" means stdin.
+    pub input: Option<String>,
+
+    // How many times to run each part when benchmarking
+    pub runs: usize,
+
+    // How to print the results of a run
+    pub format: Format,
+
+    // How many parts to run at the same time
+    pub jobs: usize,
+
+    // How long a solver can run before it's reported as failing
+    pub time_limit: Option<Duration>,
+
+    // How much slower than usual, as a fraction, counts as a regression
+    pub threshold: f64,
+
+    // What to start the random number generator with, when making an input
+    pub seed: u64,
+
+    // How big a generated input should be, if not the usual size
+    pub size: Option<usize>,
+}
+
+/// Parses the command-line arguments, not including the program name.
+/// `available` has the year and number of every day there is.
+pub fn parse_args(args: &[String], available: &[(usize, usize)]) -> AdventResult<Options> {
+    let mut iter = args.iter().peekable();
+    let command = match iter.peek().map(|s| &s[..]) {
+        Some("bench") => {
+            iter.next();
+            Command::Bench
+        }
+        Some("new") => {
+            iter.next();
+            Command::New
+        }
+        Some("watch") => {
+            iter.next();
+            Command::Watch
+        }
+        Some("history") => {
+            iter.next();
+            Command::History
+        }
+        Some("check") => {
+            iter.next();
+            Command::Check
+        }
+        Some("gen") => {
+            iter.next();
+            Command::Gen
+        }
+        Some("diff") => {
+            iter.next();
+            Command::Diff
+        }
+        _ => Command::Run,
+    };
+    // Commands that default to all days can go straight to the options
+    let defaults_to_all = matches!(command, Command::Bench | Command::Diff);
+    let selector = match iter.peek().map(|s| &s[..]) {
+        None if defaults_to_all => "all",
+        Some(s) if defaults_to_all && s.starts_with("--") => "all",
+        None => return Err(Box::new(AdventError::new("no day given"))),
+        Some(s) => {
+            iter.next();
+            s
+        }
+    };
+    let mut input = None;
+    if command == Command::Check {
+        match iter.next() {
+            Some(file) => input = Some(file.to_string()),
+            None => return Err(Box::new(AdventError::new("no input file to check"))),
+        }
+    }
+    let mut part: Option<Part> = None;
+    let mut runs = None;
+    let mut format = Format::Text;
+    let mut jobs = 1;
+    let mut time_limit = Some(DEFAULT_TIME_LIMIT);
+    let mut year = None;
+    let mut threshold = DEFAULT_THRESHOLD;
+    let mut seed: u64 = 0;
+    let mut size = None;
+    while let Some(arg) = iter.next() {
+        let mut value = || {
+            iter.next()
+                .ok_or_else(|| AdventError::new(&format!("missing value for {}", arg)))
+        };
+        match &arg[..] {
+            "--part" if !matches!(command, Command::New | Command::Gen) => {
+                part = Some(value()?.parse()?)
+            }
+            "--input" if matches!(command, Command::Run | Command::Bench) => {
+                input = Some(value()?.to_string())
+            }
+            "--runs" if matches!(command, Command::Bench | Command::Diff) => {
+                runs = Some(value()?.parse()?)
+            }
+            "--format" if command == Command::Run => format = value()?.parse()?,
+            "--jobs" if command == Command::Run => jobs = value()?.parse()?,
+            "--timeout"
+                if matches!(
+                    command,
+                    Command::Run | Command::Watch | Command::Check | Command::Diff
+                ) =>
+            {
+                time_limit = parse_time_limit(value()?)?
+            }
+            "--year" if command == Command::New => year = Some(value()?.parse()?),
+            "--threshold" if command == Command::History => threshold = parse_threshold(value()?)?,
+            "--seed" if matches!(command, Command::Gen | Command::Diff) => {
+                seed = value()?.parse()?
+            }
+            "--size" if matches!(command, Command::Gen | Command::Diff) => {
+                size = Some(value()?.parse()?)
+            }
+            _ => {
+                return Err(Box::new(AdventError::new(&format!(
+                    "unknown argument: {}",
+                    arg
+                ))))
+            }
+        }
+    }
+    let mut selection = if command == Command::New {
+        // The new day isn't available yet, so it can't be looked up
+        let day = parse_day(selector)?;
+        let year = match year {
+            Some(year) => year,
+            None => available
+                .iter()
+                .map(|&(year, _)| year)
+                .max()
+                .unwrap_or(FIRST_YEAR),
+        };
+        if year < FIRST_YEAR {
+            return Err(Box::new(AdventError::new(&format!(
+                "Advent of Code started in {}",
+                FIRST_YEAR
+            ))));
+        }
+        Part::both()
+            .iter()
+            .map(|&part| Selection { year, day, part })
+            .collect()
+    } else {
+        parse_selector(selector, available)?
+    };
+    if let Some(part) = part {
+        selection.retain(|s| s.part == part);
+        if selection.is_empty() {
+            return Err(Box::new(AdventError::new(&format!(
+                "nothing left to run with --part {}",
+                part
+            ))));
+        }
+    }
+    let is_one_day = |s: &Selection| (s.year, s.day) == (selection[0].year, selection[0].day);
+    if input.is_some() && !selection.iter().all(is_one_day) {
+        return Err(Box::new(AdventError::new(if command == Command::Check {
+            "check needs a single day"
+        } else {
+            "--input needs a single day"
+        })));
+    }
+    if command == Command::Bench && input.as_deref() == Some("-") {
+        return Err(Box::new(AdventError::new("can't benchmark stdin")));
+    }
+    if runs == Some(0) {
+        return Err(Box::new(AdventError::new("--runs must be at least 1")));
+    }
+    if jobs == 0 {
+        return Err(Box::new(AdventError::new("--jobs must be at least 1")));
+    }
+    if command == Command::Gen && !selection.iter().all(is_one_day) {
+        return Err(Box::new(AdventError::new("gen needs a single day")));
+    }
+    if size == Some(0) {
+        return Err(Box::new(AdventError::new("--size must be at least 1")));
+    }
+    let runs = runs.unwrap_or(if command == Command::Diff { 20 } else { 5 });
+    // diff uses the seeds from --seed up to, but not including, --seed + --runs
+    if command == Command::Diff && seed.checked_add(runs as u64).is_none() {
+        return Err(Box::new(AdventError::new(&format!(
+            "--seed {} is too big for {} runs",
+            seed, runs
+        ))));
+    }
+    Ok(Options {
+        command,
+        selection,
+        input,
+        runs,
+        format,
+        jobs,
+        time_limit,
+        threshold,
+        seed,
+        size,
+    })
+}
+
+/// Parses a number of seconds, where 0 means no limit
+fn parse_time_limit(s: &str) -> AdventResult<Option<Duration>> {
+    let seconds: f64 = s.parse()?;
+    if !seconds.is_finite() || seconds < 0.0 {
+        return Err(Box::new(AdventError::new(&format!("bad timeout: {}", s))));
+    }
+    if seconds == 0.0 {
+        Ok(None)
+    } else {
+        Ok(Some(Duration::from_secs_f64(seconds)))
+    }
+}
+
+/// Parses a percentage, giving a fraction
+fn parse_threshold(s: &str) -> AdventResult<f64> {
+    let percent: f64 = s.parse()?;
+    if !percent.is_finite() || percent < 0.0 {
+        return Err(Box::new(AdventError::new(&format!("bad threshold: {}", s))));
+    }
+    Ok(percent / 100.0)
+}
+
+#[cfg(test)]
+fn parse(args: &[&str]) -> AdventResult<Options> {
+    let args: Vec<String> = args.iter().map(|s| s.to_string()).collect();
+    parse_args(&args, &available_2021())
+}
+
+#[cfg(test)]
+fn select(s: &str) -> Vec<Selection> {
+    parse_selector(s, &available_2021()).unwrap()
+}
+
+#[test]
+fn test_parse_args() {
+    let all = select("all");
+    assert_eq!(
+        Options {
+            command: Command::Run,
+            selection: all.clone(),
+            input: None,
+            runs: 5,
+            format: Format::Text,
+            jobs: 1,
+            time_limit: Some(DEFAULT_TIME_LIMIT),
+            threshold: DEFAULT_THRESHOLD,
+            seed: 0,
+            size: None
+        },
+        parse(&["all"]).unwrap()
+    );
+    assert_eq!(
+        Options {
+            command: Command::Run,
+            selection: select("15b"),
+            input: Some("path/to/file".to_string()),
+            runs: 5,
+            format: Format::Text,
+            jobs: 1,
+            time_limit: Some(DEFAULT_TIME_LIMIT),
+            threshold: DEFAULT_THRESHOLD,
+            seed: 0,
+            size: None
+        },
+        parse(&["15", "--part", "b", "--input", "path/to/file"]).unwrap()
+    );
+    assert_eq!(
+        Some("-"),
+        parse(&["3", "--input", "-"]).unwrap().input.as_deref()
+    );
+    assert_eq!(
+        select("3a,4a"),
+        parse(&["3-4", "--part", "a"]).unwrap().selection
+    );
+    assert!(parse(&[]).is_err());
+    assert!(parse(&["26"]).is_err());
+    assert!(parse(&["19b", "--part", "a"]).is_err());
+    assert!(parse(&["all", "--input", "x.txt"]).is_err());
+    assert!(parse(&["3-4", "--input", "x.txt"]).is_err());
+    assert!(parse(&["3a", "--input", "x.txt"]).is_ok());
+    assert!(parse(&["3", "--part"]).is_err());
+    assert!(parse(&["3", "--bogus"]).is_err());
+    assert!(parse(&["3", "--runs", "2"]).is_err());
+    assert_eq!(
+        Format::Json,
+        parse(&["all", "--format", "json"]).unwrap().format
+    );
+    assert!(parse(&["all", "--format", "xml"]).is_err());
+    assert_eq!(4, parse(&["all", "--jobs", "4"]).unwrap().jobs);
+    assert!(parse(&["all", "--jobs", "0"]).is_err());
+    assert!(parse(&["bench", "--jobs", "4"]).is_err());
+    assert_eq!(
+        Some(Duration::from_millis(1500)),
+        parse(&["all", "--timeout", "1.5"]).unwrap().time_limit
+    );
+    assert_eq!(None, parse(&["all", "--timeout", "0"]).unwrap().time_limit);
+    assert!(parse(&["all", "--timeout", "-1"]).is_err());
+}
+
+#[test]
+fn test_parse_bench_args() {
+    assert_eq!(
+        Options {
+            command: Command::Bench,
+            selection: select("all"),
+            input: None,
+            runs: 5,
+            format: Format::Text,
+            jobs: 1,
+            time_limit: Some(DEFAULT_TIME_LIMIT),
+            threshold: DEFAULT_THRESHOLD,
+            seed: 0,
+            size: None
+        },
+        parse(&["bench"]).unwrap()
+    );
+    assert_eq!(
+        Options {
+            command: Command::Bench,
+            selection: select("19a"),
+            input: None,
+            runs: 10,
+            format: Format::Text,
+            jobs: 1,
+            time_limit: Some(DEFAULT_TIME_LIMIT),
+            threshold: DEFAULT_THRESHOLD,
+            seed: 0,
+            size: None
+        },
+        parse(&["bench", "19", "--part", "a", "--runs", "10"]).unwrap()
+    );
+    assert_eq!(
+        select("all"),
+        parse(&["bench", "--runs", "3"]).unwrap().selection
+    );
+    assert!(parse(&["bench", "19", "--runs", "0"]).is_err());
+    assert!(parse(&["bench", "19", "--input", "-"]).is_err());
+}
+
+#[test]
+fn test_parse_new_args() {
+    let options = parse(&["new", "12", "--year", "2022"]).unwrap();
+    assert_eq!(Command::New, options.command);
+    assert_eq!(
+        (2022, 12),
+        (options.selection[0].year, options.selection[0].day)
+    );
+    assert_eq!(2021, parse(&["new", "5"]).unwrap().selection[0].year);
+    assert!(parse(&["new", "1", "--year", "2014"]).is_err());
+    assert!(parse(&["new"]).is_err());
+    assert!(parse(&["new", "3-4"]).is_err());
+    assert!(parse(&["watch", "3-4", "--part", "b", "--timeout", "5"]).is_ok());
+    assert!(parse(&["watch", "3", "--input", "x.txt"]).is_err());
+    assert!(parse(&["watch"]).is_err());
+    assert!(parse(&["new", "3a"]).is_err());
+    assert!(parse(&["new", "3", "--part", "a"]).is_err());
+    assert!(parse(&["3", "--year", "2021"]).is_err());
+}
+
+#[test]
+fn test_parse_history_args() {
+    let options = parse(&["history", "3-4", "--part", "a"]).unwrap();
+    assert_eq!(Command::History, options.command);
+    assert_eq!(select("3a,4a"), options.selection);
+    assert_eq!(DEFAULT_THRESHOLD, options.threshold);
+    assert_eq!(
+        0.1,
+        parse(&["history", "all", "--threshold", "10"])
+            .unwrap()
+            .threshold
+    );
+    assert!(parse(&["history"]).is_err());
+    assert!(parse(&["history", "3", "--threshold", "-5"]).is_err());
+    assert!(parse(&["history", "3", "--timeout", "5"]).is_err());
+    assert!(parse(&["3", "--threshold", "10"]).is_err());
+}
+
+#[test]
+fn test_parse_check_args() {
+    let options = parse(&["check", "8", "path/to/file"]).unwrap();
+    assert_eq!(Command::Check, options.command);
+    assert_eq!(select("8"), options.selection);
+    assert_eq!(Some("path/to/file"), options.input.as_deref());
+    assert_eq!(
+        select("8b"),
+        parse(&["check", "8", "-", "--part", "b"])
+            .unwrap()
+            .selection
+    );
+    assert!(parse(&["check", "8"]).is_err());
+    assert!(parse(&["check", "8-9", "path/to/file"]).is_err());
+    assert!(parse(&["check", "8", "path/to/file", "--input", "x.txt"]).is_err());
+}
+
+#[test]
+fn test_parse_gen_args() {
+    let options = parse(&["gen", "19", "--seed", "7", "--size", "12"]).unwrap();
+    assert_eq!(Command::Gen, options.command);
+    assert_eq!(select("19"), options.selection);
+    assert_eq!((7, Some(12)), (options.seed, options.size));
+    assert_eq!((0, None), {
+        let options = parse(&["gen", "9"]).unwrap();
+        (options.seed, options.size)
+    });
+    assert!(parse(&["gen", "9-10"]).is_err());
+    assert!(parse(&["gen", "9", "--size", "0"]).is_err());
+    assert!(parse(&["gen", "9", "--part", "a"]).is_err());
+    assert!(parse(&["9", "--seed", "7"]).is_err());
+}
+
+#[test]
+fn test_parse_diff_args() {
+    let options = parse(&["diff"]).unwrap();
+    assert_eq!(Command::Diff, options.command);
+    assert_eq!(select("all"), options.selection);
+    assert_eq!((0, 20, None), (options.seed, options.runs, options.size));
+    assert_eq!(200, parse(&["diff", "--runs", "200"]).unwrap().runs);
+    let options = parse(&[
+        "diff", "22", "--part", "b", "--seed", "100", "--runs", "5", "--size", "30",
+    ])
+    .unwrap();
+    assert_eq!(select("22b"), options.selection);
+    assert_eq!(
+        (100, 5, Some(30)),
+        (options.seed, options.runs, options.size)
+    );
+    assert!(parse(&["diff", "22", "--runs", "0"]).is_err());
+    assert!(parse(&["diff", "22", "--input", "x.txt"]).is_err());
+
+    // The last seed has to fit
+    let last_start = (u64::MAX - 20).to_string();
+    assert_eq!(
+        u64::MAX - 20,
+        parse(&["diff", "22", "--seed", &last_start]).unwrap().seed
+    );
+    let too_big = (u64::MAX - 19).to_string();
+    assert!(parse(&["diff", "22", "--seed", &too_big]).is_err());
+    assert!(parse(&["diff", "22", "--seed", &last_start, "--runs", "21"]).is_err());
+    assert_eq!(
+        u64::MAX,
+        parse(&["gen", "22", "--seed", &u64::MAX.to_string()])
+            .unwrap()
+            .seed
+    );
+}
@@ -0,0 +1,160 @@
+use std::ops::Range;
+use std::time::Duration;
+
+use crate::generate::generate_input;
+use crate::input::Input;
+use crate::types::{AdventError, AdventResult, Answer, Day, DayPart, Part};
+use crate::verify::solve_input;
+
+/// Does the day have a reference solver for the part?
+pub fn has_reference(day: &Day, part: Part) -> bool {
+    day.reference.and_then(|r| r.part(part)).is_some()
+}
+
+/// A generated input that a solver and its reference solver don't agree on
+#[derive(Debug)]
+pub struct Disagreement {
+    pub seed: u64,
+    pub size: usize,
+    pub solver: Result<Answer, String>,
+    pub reference: Result<Answer, String>,
+}
+
+/// Two solvers agree if they get the same answer, or if they both fail.
+/// Their error messages don't have to match.
+fn agree(a: &Result<Answer, String>, b: &Result<Answer, String>) -> bool {
+    match (a, b) {
+        (Ok(a), Ok(b)) => a == b,
+        (Err(_), Err(_)) => true,
+        _ => false,
+    }
+}
+
+/// Runs one part of a day, and its reference solver, on an input generated
+/// from each of the seeds, and returns every input they disagree on.  The
+/// size of the inputs is the one the reference solvers can handle, unless
+/// one is given.
+pub fn diff_day_part(
+    day: &Day,
+    part: Part,
+    seeds: Range<u64>,
+    size: Option<usize>,
+    time_limit: Option<Duration>,
+) -> AdventResult<Vec<Disagreement>> {
+    let reference = day
+        .reference
+        .ok_or_else(|| AdventError::new(&format!("{} has no reference solvers", day)))?;
+    let reference_solver = reference.part(part).ok_or_else(|| {
+        AdventError::new(&format!("{} part {} has no reference solver", day, part))
+    })?;
+    let size = size.unwrap_or(reference.size);
+    let mut result = Vec::new();
+    for seed in seeds {
+        let input = Input::text(&(generate_input(day, seed, Some(size))?.join("\n") + "\n"));
+        let (solver, _) = solve_input(day.part(part), input.clone(), time_limit);
+        let (reference, _) = solve_input(&DayPart::new(reference_solver), input, time_limit);
+        if !agree(&solver, &reference) {
+            result.push(Disagreement {
+                seed,
+                size,
+                solver,
+                reference,
+            });
+        }
+    }
+    Ok(result)
+}
+
+/// Prints what happened when one part of a day was checked against its
+/// reference solver, with a way to make each input they disagree on
+pub fn print_diff(day: &Day, part: Part, input_count: u64, disagreements: &[Disagreement]) {
+    let outcome = |answer: &Result<Answer, String>| match answer {
+        Ok(answer) => answer.to_string(),
+        Err(message) => message.clone(),
+    };
+    for d in disagreements {
+        println!(
+            "{} part {}: seed {}, size {}: solver says {}, reference says {}",
+            day,
+            part,
+            d.seed,
+            d.size,
+            outcome(&d.solver),
+            outcome(&d.reference)
+        );
+        println!(
+            "    advent gen {}/{} --seed {} --size {}",
+            day.year, day.number, d.seed, d.size
+        );
+    }
+    println!(
+        "{} part {}: {} of {} inputs agree",
+        day,
+        part,
+        input_count - disagreements.len() as u64,
+        input_count
+    );
+}
+
+#[test]
+fn test_diff_day_part() {
+    use crate::generate::Random;
+
+    fn generate(random: &mut Random, size: usize) -> Vec<String> {
+        (0..size).map(|_| random.range(1..=9).to_string()).collect()
+    }
+    fn sum(lines: &[&str]) -> AdventResult<Answer> {
+        let numbers = crate::types::parse_lines(lines, 1, |line| Ok(line.parse::<u64>()?))?;
+        Ok(numbers.iter().sum::<u64>().into())
+    }
+    fn sum_wrong_for_nines(lines: &[&str]) -> AdventResult<Answer> {
+        let numbers = crate::types::parse_lines(lines, 1, |line| Ok(line.parse::<u64>()?))?;
+        Ok(numbers.iter().filter(|&&n| n != 9).sum::<u64>().into())
+    }
+
+    let day = Day::new(
+        2021,
+        1,
+        DayPart::new(sum),
+        DayPart::new(sum_wrong_for_nines),
+    )
+    .with_generator(generate, 100);
+    assert!(diff_day_part(&day, Part::A, 0..5, None, None).is_err());
+
+    let day = day.with_reference(Some(sum), None, 3);
+    assert!(has_reference(&day, Part::A));
+    assert!(!has_reference(&day, Part::B));
+    assert!(diff_day_part(&day, Part::A, 0..20, None, None)
+        .unwrap()
+        .is_empty());
+    assert!(diff_day_part(&day, Part::B, 0..20, None, None).is_err());
+
+    let day = Day::new(
+        2021,
+        1,
+        DayPart::new(sum_wrong_for_nines),
+        DayPart::new(sum),
+    )
+    .with_generator(generate, 100)
+    .with_reference(Some(sum), None, 3);
+    let disagreements = diff_day_part(&day, Part::A, 0..20, None, None).unwrap();
+    assert!(!disagreements.is_empty());
+    for d in disagreements {
+        assert_eq!(3, d.size);
+        let lines = generate_input(&day, d.seed, Some(d.size)).unwrap();
+        assert!(lines.contains(&"9".to_string()));
+    }
+}
+
+#[test]
+fn test_reference_solvers() {
+    // Every day's solvers agree with its reference solvers, on a few inputs
+    for day in crate::days() {
+        for part in Part::both() {
+            if has_reference(&day, part) {
+                let disagreements = diff_day_part(&day, part, 0..3, None, None).unwrap();
+                assert!(disagreements.is_empty(), "{:?}", disagreements);
+            }
+        }
+    }
+}
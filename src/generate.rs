@@ -0,0 +1,47 @@
+use crate::types::{AdventError, AdventResult, Day};
+
+pub use crate::random::Random;
+
+/// Makes a random input for the day, using its default size if none is
+/// given.  The same seed and size always make the same input.
+pub fn generate_input(day: &Day, seed: u64, size: Option<usize>) -> AdventResult<Vec<String>> {
+    let generator = day
+        .generator
+        .ok_or_else(|| AdventError::new(&format!("{} has no generator", day)))?;
+    let size = size.unwrap_or(generator.default_size);
+    if size == 0 {
+        return Err(Box::new(AdventError::new("the size must be at least 1")));
+    }
+    Ok((generator.generate)(&mut Random::new(seed), size))
+}
+
+#[test]
+fn test_generated_inputs() {
+    use crate::input::Input;
+    use crate::types::Part;
+    use crate::verify::solve_input;
+
+    for day in crate::days() {
+        // New days don't have generators yet
+        if day.generator.is_none() {
+            continue;
+        }
+        // Sorting the amphipods takes minutes without optimizations
+        if (day.year, day.number) == (2021, 23) {
+            continue;
+        }
+        let lines = generate_input(&day, 1, Some(5)).unwrap();
+        assert_eq!(lines, generate_input(&day, 1, Some(5)).unwrap());
+
+        // They're the kind of input the solvers expect
+        let strs: Vec<&str> = lines.iter().map(|s| &s[..]).collect();
+        if let Some(validate) = day.validate {
+            assert!(validate(&strs).is_empty(), "{}", day);
+        }
+        let input = Input::text(&(lines.join("\n") + "\n"));
+        for part in Part::both() {
+            let (answer, _) = solve_input(day.part(part), input.clone(), None);
+            assert!(answer.is_ok(), "{} part {}: {:?}", day, part, answer);
+        }
+    }
+}
@@ -1,28 +1,130 @@
-use ndarray::{Array, ArrayBase, Dim, OwnedRepr, ShapeBuilder};
+use ndarray::{Array, Array2, ShapeBuilder};
 use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::types::{AdventError, AdventResult};
 
 /// One point in a grid
 pub type Point = (usize, usize);
 
-pub fn parse_point(s: &str) -> Point {
-    let mut parts = s.split(",");
-    (
-        parts.next().unwrap().parse().unwrap(),
-        parts.next().unwrap().parse().unwrap(),
-    )
+/// Parses a point like "3,7"
+pub fn parse_point(s: &str) -> AdventResult<Point> {
+    match s.split_once(',') {
+        Some((x, y)) => Ok((x.parse()?, y.parse()?)),
+        None => Err(Box::new(AdventError::new("expected a point like 3,7"))),
+    }
 }
 
 #[test]
 fn test_parse_point() {
-    assert_eq!((3, 7), parse_point("3,7"));
+    assert_eq!((3, 7), parse_point("3,7").unwrap());
+    assert!(parse_point("3").is_err());
+    assert!(parse_point("3,x").is_err());
+    assert!(parse_point("3,4,5").is_err());
+}
+
+/// How the edges of a grid behave.  A grid can wrap around from one side
+/// to the other, like a torus, in either direction, or both, or neither.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Topology {
+    pub wrap_x: bool,
+    pub wrap_y: bool,
+}
+
+impl Topology {
+    /// Nothing is past the edges
+    pub const BOUNDED: Topology = Topology {
+        wrap_x: false,
+        wrap_y: false,
+    };
+
+    /// Going off any edge comes back on the opposite one
+    pub const TORUS: Topology = Topology {
+        wrap_x: true,
+        wrap_y: true,
+    };
 }
 
-/// State for the iterator over the neighbors of a cell in a grid
+/// One of the four ways to go from a cell to the one next to it
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    /// How x and y change going this way
+    pub fn offset(self) -> (isize, isize) {
+        match self {
+            Direction::Up => (0, -1),
+            Direction::Down => (0, 1),
+            Direction::Left => (-1, 0),
+            Direction::Right => (1, 0),
+        }
+    }
+
+    pub fn opposite(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+}
+
+/// Moves one coordinate by -1, 0, or 1, going around if the axis wraps
+fn step_coordinate(n: usize, delta: isize, size: usize, wrap: bool) -> Option<usize> {
+    // There's nowhere to go, or wrap around to, in an empty grid
+    if size == 0 {
+        return None;
+    }
+    match (n.checked_add_signed(delta), wrap) {
+        (Some(moved), _) if moved < size => Some(moved),
+        (_, true) => Some((n as isize + delta).rem_euclid(size as isize) as usize),
+        (_, false) => None,
+    }
+}
+
+/// Where moving by an offset from a point goes, in a grid of the given
+/// size, or None if it's off of an edge that doesn't wrap
+fn step_in(
+    shape: (usize, usize),
+    topology: Topology,
+    pos: Point,
+    offset: (isize, isize),
+) -> Option<Point> {
+    let x = step_coordinate(pos.0, offset.0, shape.0, topology.wrap_x)?;
+    let y = step_coordinate(pos.1, offset.1, shape.1, topology.wrap_y)?;
+    Some((x, y))
+}
+
+/// The offsets to the neighbors of a cell, going clockwise from the left.
+/// The odd ones are diagonals.
+const NEIGHBOR_OFFSETS: [(isize, isize); 8] = [
+    (-1, 0),
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (1, 0),
+    (1, 1),
+    (0, 1),
+    (-1, 1),
+];
+
+/// State for the iterator over the neighbors of a cell in a grid.  In a
+/// grid that wraps around, and is only one or two cells wide, a cell can
+/// be its own neighbor, or the same neighbor twice.
 pub struct Neighbors {
     // the size of the grid
     width: usize,
     height: usize,
 
+    // which edges wrap around
+    topology: Topology,
+
     // the point whose neighbors we want
     x: usize,
     y: usize,
@@ -34,78 +136,45 @@ pub struct Neighbors {
     i: usize,
 }
 
+impl Neighbors {
+    fn new(
+        shape: (usize, usize),
+        topology: Topology,
+        pos: Point,
+        include_diagonals: bool,
+    ) -> Neighbors {
+        Neighbors {
+            width: shape.0,
+            height: shape.1,
+            topology,
+            x: pos.0,
+            y: pos.1,
+            include_diagonals,
+            i: 0,
+        }
+    }
+}
+
 impl Iterator for Neighbors {
     type Item = (usize, usize);
     fn next(&mut self) -> Option<(usize, usize)> {
-        let at_left = self.x == 0;
-        let at_right = self.x == self.width - 1;
-        let at_top = self.y == 0;
-        let at_bottom = self.y == self.height - 1;
-        loop {
+        while self.i < NEIGHBOR_OFFSETS.len() {
+            let index = self.i;
             self.i += 1;
-            match self.i {
-                // the cell to the left
-                1 => {
-                    if !at_left {
-                        return Some((self.x - 1, self.y));
-                    }
-                }
-
-                // diagonal: up and left
-                2 => {
-                    if self.include_diagonals && !at_left && !at_top {
-                        return Some((self.x - 1, self.y - 1));
-                    }
-                }
-
-                // the cell above
-                3 => {
-                    if !at_top {
-                        return Some((self.x, self.y - 1));
-                    }
-                }
-
-                // diagonal: up and right
-                4 => {
-                    if self.include_diagonals && !at_right && !at_top {
-                        return Some((self.x + 1, self.y - 1));
-                    }
-                }
-
-                // the cell to the right
-                5 => {
-                    if !at_right {
-                        return Some((self.x + 1, self.y));
-                    }
-                }
-
-                // diagonal: down and right
-                6 => {
-                    if self.include_diagonals && !at_right && !at_bottom {
-                        return Some((self.x + 1, self.y + 1));
-                    }
-                }
-
-                // the cell below
-                7 => {
-                    if !at_bottom {
-                        return Some((self.x, self.y + 1));
-                    }
-                }
-
-                // diagonal: down and right
-                8 => {
-                    if self.include_diagonals && !at_left && !at_bottom {
-                        return Some((self.x - 1, self.y + 1));
-                    }
-                }
-
-                // all done
-                _ => {
-                    return None;
-                }
+            if index % 2 == 1 && !self.include_diagonals {
+                continue;
+            }
+            let neighbor = step_in(
+                (self.width, self.height),
+                self.topology,
+                (self.x, self.y),
+                NEIGHBOR_OFFSETS[index],
+            );
+            if neighbor.is_some() {
+                return neighbor;
             }
         }
+        None
     }
 }
 
@@ -117,6 +186,7 @@ fn test_neighbors_no_diagonals() {
             height,
             x,
             y,
+            topology: Topology::BOUNDED,
             include_diagonals: false,
             i: 0,
         }
@@ -135,6 +205,7 @@ fn test_neighbors_with_diagonals() {
             height,
             x,
             y,
+            topology: Topology::BOUNDED,
             include_diagonals: true,
             i: 0,
         }
@@ -157,23 +228,85 @@ fn test_neighbors_with_diagonals() {
     );
 }
 
-#[derive(PartialEq)]
-pub struct Grid {
-    values: ArrayBase<OwnedRepr<u8>, Dim<[usize; 2]>>,
+/// A rectangle of cells, indexed by (x, y), with (0, 0) at the top left.
+/// The cells can be anything; grids of digits are the most common.  Grids
+/// don't wrap around unless they're given a topology that does.
+#[derive(Clone, PartialEq)]
+pub struct Grid<T = u8> {
+    values: Array2<T>,
+    topology: Topology,
 }
 
-impl Grid {
-    pub fn zeros(shape: (usize, usize)) -> Grid {
-        let values = ArrayBase::zeros(shape);
-        Grid { values }
+impl<T> Grid<T> {
+    /// A grid with the same value in every cell
+    pub fn filled(shape: (usize, usize), value: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            values: Array2::from_elem(shape, value),
+            topology: Topology::BOUNDED,
+        }
     }
 
-    pub fn get(&self, pos: Point) -> u8 {
-        self.values[pos]
+    /// A grid whose cells are made by calling a function with each point
+    pub fn from_fn(shape: (usize, usize), f: impl FnMut(Point) -> T) -> Grid<T> {
+        Grid {
+            values: Array2::from_shape_fn(shape, f),
+            topology: Topology::BOUNDED,
+        }
     }
 
-    pub fn set(&mut self, pos: Point, new_value: u8) {
-        self.values[pos] = new_value;
+    /// Parses a grid with one row per line, and one cell per character.
+    /// All of the rows must be the same length.  `parse_cell` turns a
+    /// character into a cell, or says it isn't one, and `expected`
+    /// describes the characters that are, like "a digit".
+    pub fn from_chars(
+        lines: &[&str],
+        parse_cell: impl Fn(char) -> Option<T>,
+        expected: &str,
+    ) -> AdventResult<Grid<T>> {
+        let columns = match lines.first() {
+            Some(line) if !line.is_empty() => line.chars().count(),
+            _ => return Err(Box::new(AdventError::new("empty grid"))),
+        };
+        let mut value_vector: Vec<T> = Vec::with_capacity(columns * lines.len());
+        for (i, line) in lines.iter().enumerate() {
+            if line.chars().count() != columns {
+                return Err(Box::new(AdventError::parse(
+                    i + 1,
+                    line,
+                    &format!("expected {} cells, like the first row", columns),
+                )));
+            }
+            for c in line.chars() {
+                match parse_cell(c) {
+                    Some(value) => value_vector.push(value),
+                    None => {
+                        return Err(Box::new(AdventError::parse(
+                            i + 1,
+                            line,
+                            &format!("not {}: {:?}", expected, c),
+                        )))
+                    }
+                }
+            }
+        }
+        let rows = lines.len();
+        let values = Array::from_shape_vec((columns, rows).strides((1, columns)), value_vector)?;
+        Ok(Grid {
+            values,
+            topology: Topology::BOUNDED,
+        })
+    }
+
+    /// The same grid, with edges that behave differently
+    pub fn with_topology(self, topology: Topology) -> Grid<T> {
+        Grid { topology, ..self }
+    }
+
+    pub fn topology(&self) -> Topology {
+        self.topology
     }
 
     pub fn shape(&self) -> (usize, usize) {
@@ -183,32 +316,134 @@ impl Grid {
         (columns, rows)
     }
 
-    pub fn neigbors(&self, pos: (usize, usize)) -> Neighbors {
-        let shape = self.values.shape();
-        Neighbors {
-            width: shape[0],
-            height: shape[1],
-            x: pos.0,
-            y: pos.1,
-            include_diagonals: false,
-            i: 0,
+    /// A grid of the same shape, with each cell changed by a function
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            values: self.values.map(f),
+            topology: self.topology,
         }
     }
 
+    /// All of the cells, and where they are, a row at a time from the top
+    pub fn iter_cells(&self) -> impl Iterator<Item = (Point, &T)> + '_ {
+        let (columns, rows) = self.shape();
+        (0..rows).flat_map(move |y| (0..columns).map(move |x| ((x, y), &self.values[(x, y)])))
+    }
+
+    /// Where the first cell that matches is, going a row at a time
+    pub fn find(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Point> {
+        self.iter_cells()
+            .find(|(_, value)| predicate(value))
+            .map(|(pos, _)| pos)
+    }
+
+    /// How many cells match
+    pub fn count(&self, mut predicate: impl FnMut(&T) -> bool) -> usize {
+        self.values.iter().filter(|value| predicate(value)).count()
+    }
+
+    /// The cell next to a cell, going one way, or None if that's off an
+    /// edge that doesn't wrap
+    pub fn step(&self, pos: Point, direction: Direction) -> Option<Point> {
+        step_in(self.shape(), self.topology, pos, direction.offset())
+    }
+
+    /// A grid where every cell is replaced, all at once, by what the rule
+    /// says, like a cellular automaton.  The rule is given this grid, and
+    /// the cell to replace, so it can look at the neighbors.
+    pub fn next_generation(&self, mut rule: impl FnMut(&Grid<T>, Point) -> T) -> Grid<T> {
+        Grid {
+            values: Array2::from_shape_fn(self.values.dim(), |pos| rule(self, pos)),
+            topology: self.topology,
+        }
+    }
+
+    /// Takes steps until one doesn't change anything, or there have been
+    /// `max_steps`.  Returns the grid that stopped changing, and how many
+    /// steps were taken, including the last one.
+    pub fn run_until_stable(
+        &self,
+        max_steps: usize,
+        mut step: impl FnMut(&Grid<T>) -> Grid<T>,
+    ) -> Option<(Grid<T>, usize)>
+    where
+        T: PartialEq,
+    {
+        let mut current = step(self);
+        if current == *self {
+            return Some((current, 1));
+        }
+        for count in 2..=max_steps {
+            let next = step(&current);
+            if next == current {
+                return Some((next, count));
+            }
+            current = next;
+        }
+        None
+    }
+
+    pub fn neigbors(&self, pos: (usize, usize)) -> Neighbors {
+        Neighbors::new(self.shape(), self.topology, pos, false)
+    }
+
     pub fn neigbors_with_diagonals(&self, pos: (usize, usize)) -> Neighbors {
-        let shape = self.values.shape();
-        Neighbors {
-            width: shape[0],
-            height: shape[1],
-            x: pos.0,
-            y: pos.1,
-            include_diagonals: true,
-            i: 0,
+        Neighbors::new(self.shape(), self.topology, pos, true)
+    }
+}
+
+impl<T: Copy> Grid<T> {
+    pub fn get(&self, pos: Point) -> T {
+        self.values[pos]
+    }
+
+    pub fn set(&mut self, pos: Point, new_value: T) {
+        self.values[pos] = new_value;
+    }
+
+    /// A view of this grid repeated `tiles` times across and down, without
+    /// copying it.  The values in each tile are changed by `transform`,
+    /// which is given a value and which tile it's in, counting from the
+    /// top left tile at (0, 0).
+    pub fn tiled<F: Fn(T, Point) -> T>(
+        &self,
+        tiles: (usize, usize),
+        transform: F,
+    ) -> Tiled<'_, T, F> {
+        Tiled {
+            grid: self,
+            tiles,
+            transform,
         }
     }
 }
 
-impl fmt::Debug for Grid {
+impl Grid<u8> {
+    pub fn zeros(shape: (usize, usize)) -> Grid {
+        Grid::filled(shape, 0)
+    }
+
+    /// Parses a grid of digits, with one row per line
+    pub fn from_digits(lines: &[&str]) -> AdventResult<Grid> {
+        Grid::from_chars(lines, |c| c.to_digit(10).map(|d| d as u8), "a digit")
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Point) -> &T {
+        &self.values[pos]
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, pos: Point) -> &mut T {
+        &mut self.values[pos]
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Grid<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let (columns, rows) = self.shape();
         for y in 0..rows {
@@ -218,31 +453,297 @@ impl fmt::Debug for Grid {
                 }
                 write!(f, "{:?}", self.values[(x, y)])?;
             }
-            write!(f, "\n")?;
+            writeln!(f)?;
         }
         Ok(())
     }
 }
 
-pub fn parse_grid(lines: &[&str]) -> Grid {
-    let value_vector: Vec<u8> = lines
-        .iter()
-        .map(|line| line.chars())
-        .flatten()
-        .map(|c| (c as u8) - b'0')
-        .collect();
-    let rows = lines.len();
-    let columns = value_vector.len() / rows;
-    if value_vector.len() != rows * columns {
-        panic!("size didn't work");
+/// Anything that can be read like a grid: a shape, a value in every cell,
+/// and edges that might wrap around.  The values don't have to be stored;
+/// a view can work each one out when it's asked for.
+pub trait GridView {
+    type Cell;
+
+    fn shape(&self) -> (usize, usize);
+
+    fn get(&self, pos: Point) -> Self::Cell;
+
+    fn topology(&self) -> Topology {
+        Topology::BOUNDED
+    }
+
+    fn neigbors(&self, pos: Point) -> Neighbors {
+        Neighbors::new(self.shape(), self.topology(), pos, false)
+    }
+
+    fn neigbors_with_diagonals(&self, pos: Point) -> Neighbors {
+        Neighbors::new(self.shape(), self.topology(), pos, true)
+    }
+
+    /// A grid holding a copy of every value
+    fn to_grid(&self) -> Grid<Self::Cell> {
+        Grid::from_fn(self.shape(), |pos| self.get(pos)).with_topology(self.topology())
+    }
+}
+
+impl<T: Copy> GridView for Grid<T> {
+    type Cell = T;
+
+    fn shape(&self) -> (usize, usize) {
+        Grid::shape(self)
+    }
+
+    fn get(&self, pos: Point) -> T {
+        self.values[pos]
+    }
+
+    fn topology(&self) -> Topology {
+        self.topology
+    }
+}
+
+/// A grid repeated across and down, made by `Grid::tiled`.  The edges of
+/// the whole thing wrap around if the grid's edges do.
+pub struct Tiled<'a, T, F> {
+    grid: &'a Grid<T>,
+    tiles: (usize, usize),
+    transform: F,
+}
+
+impl<T: Copy, F: Fn(T, Point) -> T> GridView for Tiled<'_, T, F> {
+    type Cell = T;
+
+    fn shape(&self) -> (usize, usize) {
+        let (columns, rows) = self.grid.shape();
+        (columns * self.tiles.0, rows * self.tiles.1)
+    }
+
+    fn get(&self, (x, y): Point) -> T {
+        let (columns, rows) = self.grid.shape();
+        let tile = (x / columns, y / rows);
+        assert!(
+            tile.0 < self.tiles.0 && tile.1 < self.tiles.1,
+            "({}, {}) is outside the tiles",
+            x,
+            y
+        );
+        (self.transform)(self.grid.values[(x % columns, y % rows)], tile)
+    }
+
+    fn topology(&self) -> Topology {
+        self.grid.topology
     }
-    let values =
-        Array::from_shape_vec((columns, rows).strides((1, columns)), value_vector).unwrap();
-    Grid { values }
+}
+
+#[test]
+fn test_wrapping() {
+    let grid = Grid::from_digits(&["123", "456", "789"]).unwrap();
+    assert_eq!(None, grid.step((0, 1), Direction::Left));
+    assert_eq!(Some((1, 1)), grid.step((0, 1), Direction::Right));
+    assert_eq!(
+        vec![(1, 0), (0, 1)],
+        grid.neigbors((0, 0)).collect::<Vec<_>>()
+    );
+
+    let torus = grid.clone().with_topology(Topology::TORUS);
+    assert_eq!(Some((2, 1)), torus.step((0, 1), Direction::Left));
+    assert_eq!(Some((0, 2)), torus.step((0, 0), Direction::Up));
+    assert_eq!(
+        vec![(2, 0), (0, 2), (1, 0), (0, 1)],
+        torus.neigbors((0, 0)).collect::<Vec<_>>()
+    );
+    assert_eq!(8, torus.neigbors_with_diagonals((0, 0)).count());
+
+    // Wrapping one way, but not the other
+    let cylinder = grid.with_topology(Topology {
+        wrap_x: true,
+        wrap_y: false,
+    });
+    assert_eq!(Some((2, 0)), cylinder.step((0, 0), Direction::Left));
+    assert_eq!(None, cylinder.step((0, 0), Direction::Up));
+    assert_eq!(5, cylinder.neigbors_with_diagonals((0, 0)).count());
+
+    // An empty grid has nothing to wrap around to
+    for shape in [(0, 3), (3, 0), (0, 0)] {
+        let empty = Grid::zeros(shape).with_topology(Topology::TORUS);
+        assert_eq!(None, empty.step((0, 0), Direction::Left));
+        assert_eq!(0, empty.neigbors_with_diagonals((0, 0)).count());
+    }
+}
+
+#[test]
+fn test_automaton() {
+    // Every cell becomes the cell to its left, so everything moves right
+    let grid = Grid::from_digits(&["120", "300"])
+        .unwrap()
+        .with_topology(Topology::TORUS);
+    let shift = |grid: &Grid| {
+        grid.next_generation(|grid, pos| grid[grid.step(pos, Direction::Left).unwrap()])
+    };
+    assert_eq!("0 1 2\n0 3 0\n", format!("{:?}", shift(&grid)));
+    assert_eq!(Topology::TORUS, shift(&grid).topology());
+
+    // Cells fall until they land on something
+    let grid = Grid::from_digits(&["10", "01", "00"]).unwrap();
+    let fall = |grid: &Grid| {
+        grid.next_generation(|grid, pos| {
+            let above = grid.step(pos, Direction::Up);
+            let below = grid.step(pos, Direction::Down);
+            match (grid[pos], above.map(|p| grid[p]), below.map(|p| grid[p])) {
+                (0, Some(1), _) => 1,
+                (1, _, Some(0)) => 0,
+                (cell, _, _) => cell,
+            }
+        })
+    };
+    let (settled, steps) = grid.run_until_stable(10, fall).unwrap();
+    assert_eq!("0 0\n0 0\n1 1\n", format!("{:?}", settled));
+    assert_eq!(3, steps);
+    assert!(grid.run_until_stable(2, fall).is_none());
+    assert_eq!(1, settled.run_until_stable(1, fall).unwrap().1);
+}
+
+#[test]
+fn test_tiled() {
+    let grid = Grid::from_digits(&["12", "34", "56"]).unwrap();
+    let tiled = grid.tiled((3, 2), |v, (tx, ty)| v + 10 * tx as u8 + 100 * ty as u8);
+    assert_eq!((6, 6), tiled.shape());
+    assert_eq!(1, tiled.get((0, 0)));
+    assert_eq!(12, tiled.get((3, 0)));
+    assert_eq!(124, tiled.get((5, 4)));
+    assert_eq!(
+        vec![(1, 3), (2, 2), (3, 3), (2, 4)],
+        tiled.neigbors((2, 3)).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        "1 2 11 12 21 22\n\
+         3 4 13 14 23 24\n\
+         5 6 15 16 25 26\n\
+         101 102 111 112 121 122\n\
+         103 104 113 114 123 124\n\
+         105 106 115 116 125 126\n",
+        format!("{:?}", tiled.to_grid())
+    );
+
+    // A plain grid is a view of itself
+    assert_eq!(grid, GridView::to_grid(&grid));
+
+    // The tiles wrap around as a whole
+    let torus = grid.with_topology(Topology::TORUS);
+    let tiled = torus.tiled((2, 2), |v, _| v);
+    assert_eq!(
+        vec![(3, 0), (0, 5), (1, 0), (0, 1)],
+        tiled.neigbors((0, 0)).collect::<Vec<_>>()
+    );
+}
+
+/// Parses a grid of digits, with one row per line.  All of the rows must be
+/// the same length.
+pub fn parse_grid(lines: &[&str]) -> AdventResult<Grid> {
+    Grid::from_digits(lines)
 }
 
 #[test]
 fn test_parse_format_grid() {
-    let grid = parse_grid(&["123", "456"]);
+    let grid = parse_grid(&["123", "456"]).unwrap();
     assert_eq!("1 2 3\n4 5 6\n", format!("{:?}", grid));
+
+    let error = parse_grid(&["123", "45"]).unwrap_err();
+    assert_eq!(
+        "AdventError: parse error: line 2: \"45\": expected 3 cells, like the first row",
+        error.to_string()
+    );
+    assert_eq!(
+        "AdventError: parse error: line 2: \"4x6\": not a digit: 'x'",
+        parse_grid(&["123", "4x6"]).unwrap_err().to_string()
+    );
+    assert!(parse_grid(&[]).is_err());
+}
+
+#[test]
+fn test_generic_grid() {
+    #[derive(Clone, Copy, Debug, PartialEq)]
+    enum Cell {
+        Open,
+        Wall,
+    }
+    let parse_cell = |c| match c {
+        '.' => Some(Cell::Open),
+        '#' => Some(Cell::Wall),
+        _ => None,
+    };
+    let grid = Grid::from_chars(&["#.#", "..#"], parse_cell, "'.' or '#'").unwrap();
+    assert_eq!((3, 2), grid.shape());
+    assert_eq!(Cell::Wall, grid[(2, 1)]);
+    assert_eq!(3, grid.count(|&c| c == Cell::Wall));
+    assert_eq!(Some((1, 0)), grid.find(|&c| c == Cell::Open));
+    assert!(Grid::from_chars(&["#x#"], parse_cell, "'.' or '#'").is_err());
+
+    let cells: Vec<Point> = grid.iter_cells().map(|(pos, _)| pos).collect();
+    assert_eq!(vec![(0, 0), (1, 0), (2, 0), (0, 1), (1, 1), (2, 1)], cells);
+
+    let walls = grid.map(|&c| if c == Cell::Wall { 1u8 } else { 0 });
+    assert_eq!("1 0 1\n0 0 1\n", format!("{:?}", walls));
+
+    let mut sums = Grid::from_fn((2, 2), |(x, y)| x + 10 * y);
+    sums[(0, 0)] = 7;
+    assert_eq!("7 1\n10 11\n", format!("{:?}", sums));
+    assert_eq!(Grid::filled((2, 1), 'a'), Grid::from_fn((2, 1), |_| 'a'));
+}
+
+/// Finds everything wrong with the lines of a grid, where each line is a
+/// row, and each character is a cell: rows that aren't as long as the
+/// first, and cells that aren't allowed.  `allowed` describes the cells,
+/// like "digits".
+pub fn check_grid(lines: &[&str], is_allowed: fn(char) -> bool, allowed: &str) -> Vec<AdventError> {
+    let columns = match lines.first() {
+        Some(line) if !line.is_empty() => line.chars().count(),
+        _ => return vec![AdventError::new("empty grid")],
+    };
+    let mut result = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        let length = line.chars().count();
+        if length != columns {
+            result.push(AdventError::parse(
+                i + 1,
+                line,
+                &format!("{} cells, but the first row has {}", length, columns),
+            ));
+        }
+        let bad: Vec<String> = line
+            .chars()
+            .enumerate()
+            .filter(|(_, c)| !is_allowed(*c))
+            .map(|(x, c)| format!("{:?} at column {}", c, x + 1))
+            .collect();
+        if !bad.is_empty() {
+            result.push(AdventError::parse(
+                i + 1,
+                line,
+                &format!("expected {}, not {}", allowed, bad.join(", ")),
+            ));
+        }
+    }
+    result
+}
+
+#[test]
+fn test_check_grid() {
+    let is_digit = |c: char| c.is_ascii_digit();
+    assert!(check_grid(&["123", "456"], is_digit, "digits").is_empty());
+    assert_eq!(1, check_grid(&[], is_digit, "digits").len());
+
+    let problems: Vec<String> = check_grid(&["123", "4x", "7y9"], is_digit, "digits")
+        .iter()
+        .map(|e| e.to_string())
+        .collect();
+    assert_eq!(
+        vec![
+            "AdventError: parse error: line 2: \"4x\": 2 cells, but the first row has 3",
+            "AdventError: parse error: line 2: \"4x\": expected digits, not 'x' at column 2",
+            "AdventError: parse error: line 3: \"7y9\": expected digits, not 'y' at column 2",
+        ],
+        problems
+    );
 }
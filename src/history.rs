@@ -0,0 +1,261 @@
+use std::fs::{self, OpenOptions};
+use std::io::{ErrorKind, Write};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::selector::Selection;
+use crate::types::{day_name, AdventError, AdventResult};
+use crate::util::{format_duration, print_table};
+use crate::verify::RunResult;
+
+/// Where the results of every run are kept.  It has one JSON record per line,
+/// and only ever gets added to.
+pub const HISTORY_FILE: &str = "history.jsonl";
+
+/// How many of the runs before a run are used to decide how long it
+/// usually takes
+const BASELINE_RUNS: usize = 5;
+
+/// Changes in time smaller than this are noise, not regressions
+const MIN_SLOWDOWN_MS: f64 = 1.0;
+
+/// The result of running one part of one day on one input, at some time
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct HistoryRecord {
+    // Seconds since the Unix epoch
+    pub timestamp: u64,
+    pub year: usize,
+    pub day: usize,
+    pub part: String,
+    pub input: String,
+    pub answer: Option<String>,
+    pub status: String,
+    pub elapsed_ms: f64,
+}
+
+impl HistoryRecord {
+    pub fn new(result: &RunResult, timestamp: u64) -> HistoryRecord {
+        HistoryRecord {
+            timestamp,
+            year: result.year,
+            day: result.day,
+            part: result.part.to_string(),
+            input: result.input.clone(),
+            answer: result.actual.as_ref().ok().map(|a| a.to_string()),
+            status: result.status().name().to_string(),
+            elapsed_ms: result.elapsed.as_secs_f64() * 1e3,
+        }
+    }
+
+    /// Is this a record for one of the selected parts?
+    fn is_selected(&self, selection: &[Selection]) -> bool {
+        selection.iter().any(|s| {
+            (s.year, s.day, s.part.to_string()) == (self.year, self.day, self.part.clone())
+        })
+    }
+}
+
+/// Adds the results of a run to the end of the history file
+pub fn append(path: &Path, results: &[RunResult]) -> AdventResult<()> {
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    let mut text = String::new();
+    for result in results {
+        text += &serde_json::to_string(&HistoryRecord::new(result, timestamp))?;
+        text += "\n";
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(text.as_bytes())?;
+    Ok(())
+}
+
+/// Reads all of the records in the history file, oldest first.  If there's
+/// no file, there's no history.
+pub fn load(path: &Path) -> AdventResult<Vec<HistoryRecord>> {
+    let text = match fs::read_to_string(path) {
+        Ok(text) => text,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(Box::new(e)),
+    };
+    parse_history(&text).map_err(|e| AdventError::new(&format!("{}: {}", path.display(), e)).into())
+}
+
+fn parse_history(text: &str) -> AdventResult<Vec<HistoryRecord>> {
+    let mut result = Vec::new();
+    for (i, line) in text.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record = serde_json::from_str(line)
+            .map_err(|e| AdventError::new(&format!("line {}: {}", i + 1, e)))?;
+        result.push(record);
+    }
+    Ok(result)
+}
+
+/// What was different about a run, compared to the runs before it
+fn changes(earlier: &[&HistoryRecord], record: &HistoryRecord, threshold: f64) -> Vec<String> {
+    let mut result = Vec::new();
+    let previous_answer = earlier.last().and_then(|r| r.answer.as_ref());
+    if let (Some(previous), Some(answer)) = (previous_answer, &record.answer) {
+        if previous != answer {
+            result.push(format!("answer changed from {}", previous));
+        }
+    }
+
+    // Failed runs don't say much about how long things take
+    let mut usual: Vec<f64> = earlier
+        .iter()
+        .rev()
+        .filter(|r| r.status != "error")
+        .take(BASELINE_RUNS)
+        .map(|r| r.elapsed_ms)
+        .collect();
+    if !usual.is_empty() && record.status != "error" {
+        usual.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let median = usual[usual.len() / 2];
+        if median * (1.0 + threshold) < record.elapsed_ms
+            && MIN_SLOWDOWN_MS <= record.elapsed_ms - median
+        {
+            result.push(format!(
+                "SLOWER: {:.1}x the usual {}",
+                record.elapsed_ms / median,
+                format_ms(median)
+            ));
+        }
+    }
+    result
+}
+
+#[test]
+fn test_changes() {
+    let record = |answer: &str, elapsed_ms: f64| HistoryRecord {
+        timestamp: 0,
+        year: 2021,
+        day: 1,
+        part: "A".to_string(),
+        input: "input.txt".to_string(),
+        answer: Some(answer.to_string()),
+        status: "pass".to_string(),
+        elapsed_ms,
+    };
+    let earlier = [record("7", 10.0), record("7", 12.0), record("7", 11.0)];
+    let earlier: Vec<&HistoryRecord> = earlier.iter().collect();
+
+    assert!(changes(&earlier, &record("7", 12.0), 0.25).is_empty());
+    assert_eq!(
+        vec!["SLOWER: 2.0x the usual 11.0ms"],
+        changes(&earlier, &record("7", 22.0), 0.25)
+    );
+    assert_eq!(
+        vec!["answer changed from 7"],
+        changes(&earlier, &record("8", 11.0), 0.25)
+    );
+    assert!(changes(&[], &record("7", 22.0), 0.25).is_empty());
+
+    // Tiny times go up and down by more than the threshold all the time
+    let fast = [record("7", 0.01)];
+    let fast: Vec<&HistoryRecord> = fast.iter().collect();
+    assert!(changes(&fast, &record("7", 0.05), 0.25).is_empty());
+}
+
+fn format_ms(ms: f64) -> String {
+    format_duration(std::time::Duration::from_secs_f64(ms / 1e3))
+}
+
+/// Formats a time as a UTC date and time, like "2021-12-25 05:00:00"
+fn format_timestamp(timestamp: u64) -> String {
+    let days = (timestamp / 86400) as i64;
+    let seconds = timestamp % 86400;
+
+    // Turn days since 1970-01-01 into a date on the Gregorian calendar.
+    // See http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719468;
+    let era = z.div_euclid(146097);
+    let day_of_era = z.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!(
+        "{:04}-{:02}-{:02} {:02}:{:02}:{:02}",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+#[test]
+fn test_format_timestamp() {
+    assert_eq!("1970-01-01 00:00:00", format_timestamp(0));
+    assert_eq!("2021-12-25 05:00:00", format_timestamp(1640408400));
+    assert_eq!("2024-02-29 23:59:59", format_timestamp(1709251199));
+}
+
+/// Prints the history of the selected parts: a table of every run, oldest
+/// first, noting answers that changed and runs that were slower than usual.
+/// A run is slower than usual if it took more than `threshold` (a fraction)
+/// longer than the median of the runs just before it.
+pub fn print_history(records: &[HistoryRecord], selection: &[Selection], threshold: f64) {
+    let mut selected: Vec<&HistoryRecord> = records
+        .iter()
+        .filter(|r| r.is_selected(selection))
+        .collect();
+    if selected.is_empty() {
+        println!("No history for those days yet");
+        return;
+    }
+
+    // Group the runs of each part on each input, keeping them in time order
+    selected.sort_by(|a, b| {
+        (a.year, a.day, &a.part, &a.input, a.timestamp).cmp(&(
+            b.year,
+            b.day,
+            &b.part,
+            &b.input,
+            b.timestamp,
+        ))
+    });
+
+    let header = [
+        "when", "day", "part", "input", "answer", "time", "status", "changes",
+    ];
+    let mut rows = vec![header.iter().map(|s| s.to_string()).collect()];
+    let mut regression_count = 0;
+    for (i, record) in selected.iter().enumerate() {
+        let group_start = selected[..i]
+            .iter()
+            .rposition(|r| {
+                (r.year, r.day, &r.part, &r.input)
+                    != (record.year, record.day, &record.part, &record.input)
+            })
+            .map_or(0, |j| j + 1);
+        let notes = changes(&selected[group_start..i], record, threshold);
+        regression_count += notes.iter().filter(|n| n.starts_with("SLOWER")).count();
+        rows.push(vec![
+            format_timestamp(record.timestamp),
+            day_name(record.year, record.day),
+            record.part.clone(),
+            record.input.clone(),
+            record.answer.clone().unwrap_or_default(),
+            format_ms(record.elapsed_ms),
+            record.status.clone(),
+            notes.join("; "),
+        ]);
+    }
+    print_table(&rows);
+    println!(
+        "\n{} runs, {} slower than usual (by more than {:.0}%)",
+        selected.len(),
+        regression_count,
+        threshold * 100.0
+    );
+}
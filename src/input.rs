@@ -0,0 +1,78 @@
+use std::fmt::{Display, Formatter};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Cursor, Read};
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Where the input for a solver comes from.
+///
+/// Files are opened each time they're used, and read as the solver asks for
+/// more, so a solver that streams its input doesn't need to hold all of it
+/// in memory.
+#[derive(Clone, Debug)]
+pub enum Input {
+    File(PathBuf),
+
+    // Input that has already been read, like stdin, which can only be read once
+    Bytes(Arc<[u8]>),
+}
+
+impl Input {
+    /// The input with the given name, where "-" means stdin.  Stdin is read
+    /// right away, so that it can be solved more than once.
+    pub fn named(name: &str) -> io::Result<Input> {
+        if name == "-" {
+            let mut bytes = Vec::new();
+            io::stdin().lock().read_to_end(&mut bytes)?;
+            Ok(Input::Bytes(bytes.into()))
+        } else {
+            Ok(Input::File(PathBuf::from(name)))
+        }
+    }
+
+    /// An input made from some text
+    pub fn text(s: &str) -> Input {
+        Input::Bytes(s.as_bytes().into())
+    }
+
+    /// Starts reading the input from the beginning
+    pub fn open(&self) -> io::Result<Box<dyn BufRead>> {
+        match self {
+            Input::File(path) => Ok(Box::new(BufReader::new(File::open(path)?))),
+            Input::Bytes(bytes) => Ok(Box::new(Cursor::new(bytes.clone()))),
+        }
+    }
+
+    /// Reads all of the lines of the input
+    pub fn lines(&self) -> io::Result<Vec<String>> {
+        self.open()?.lines().collect()
+    }
+}
+
+impl Display for Input {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            Input::File(path) => write!(f, "{}", path.display()),
+            Input::Bytes(bytes) => write!(f, "{} bytes of input", bytes.len()),
+        }
+    }
+}
+
+#[test]
+fn test_input() {
+    let input = Input::text("one\ntwo\n");
+    assert_eq!(vec!["one", "two"], input.lines().unwrap());
+    // It can be read more than once
+    assert_eq!(vec!["one", "two"], input.lines().unwrap());
+
+    let sample = Input::named("input/2021/day-1/sample.txt").unwrap();
+    assert_eq!("199", sample.lines().unwrap()[0]);
+    assert!(Input::named("no/such/file").unwrap().open().is_err());
+}
+
+/// An input that has been read the way a solver wants it: either all of
+/// its lines, or a reader for a solver that streams.
+pub enum Loaded {
+    Lines(Vec<String>),
+    Reader(Box<dyn BufRead>),
+}
@@ -0,0 +1,104 @@
+/// Letters drawn with '#' and '.', in the 4x6 font that some puzzles
+/// use to spell out their answers.
+const GLYPHS: [(char, [&str; 6]); 16] = [
+    ('A', [".##.", "#..#", "#..#", "####", "#..#", "#..#"]),
+    ('B', ["###.", "#..#", "###.", "#..#", "#..#", "###."]),
+    ('C', [".##.", "#..#", "#...", "#...", "#..#", ".##."]),
+    ('E', ["####", "#...", "###.", "#...", "#...", "####"]),
+    ('F', ["####", "#...", "###.", "#...", "#...", "#..."]),
+    ('G', [".##.", "#..#", "#...", "#.##", "#..#", ".###"]),
+    ('H', ["#..#", "#..#", "####", "#..#", "#..#", "#..#"]),
+    ('J', ["..##", "...#", "...#", "...#", "#..#", ".##."]),
+    ('K', ["#..#", "#.#.", "##..", "#.#.", "#.#.", "#..#"]),
+    ('L', ["#...", "#...", "#...", "#...", "#...", "####"]),
+    ('O', [".##.", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('P', ["###.", "#..#", "#..#", "###.", "#...", "#..."]),
+    ('R', ["###.", "#..#", "#..#", "###.", "#.#.", "#..#"]),
+    ('S', [".###", "#...", "#...", ".##.", "...#", "###."]),
+    ('U', ["#..#", "#..#", "#..#", "#..#", "#..#", ".##."]),
+    ('Z', ["####", "...#", "..#.", ".#..", "#...", "####"]),
+];
+
+/// Width of one letter, including the blank column after it
+const LETTER_WIDTH: usize = 5;
+
+/// Reads the letters in a picture made of rows of '#' and '.'.
+///
+/// Returns None if the picture isn't a row of letters we know.
+pub fn read_letters(rows: &[String]) -> Option<String> {
+    if rows.len() != 6 {
+        return None;
+    }
+    let width = rows.iter().map(|r| r.len()).max().unwrap_or(0);
+    if width == 0 {
+        return None;
+    }
+    let letter_count = width.div_ceil(LETTER_WIDTH);
+    let mut result = String::new();
+    for i in 0..letter_count {
+        let start = i * LETTER_WIDTH;
+        let cell = |row: &String, x: usize| row.as_bytes().get(x).copied().unwrap_or(b'.');
+        let matches = |glyph: &[&str; 6]| {
+            rows.iter().zip(glyph.iter()).all(|(row, glyph_row)| {
+                glyph_row
+                    .bytes()
+                    .enumerate()
+                    .all(|(x, b)| cell(row, start + x) == b)
+                    && cell(row, start + 4) == b'.'
+            })
+        };
+        let (c, _) = GLYPHS.iter().find(|(_, glyph)| matches(glyph))?;
+        result.push(*c);
+    }
+    Some(result)
+}
+
+#[test]
+fn test_read_letters() {
+    let rows: Vec<String> = [
+        "#..#.####.#....",
+        "#..#.#....#....",
+        "####.###..#....",
+        "#..#.#....#....",
+        "#..#.#....#....",
+        "#..#.####.####.",
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect();
+    assert_eq!(Some("HEL".to_string()), read_letters(&rows));
+    assert_eq!(None, read_letters(&rows[1..]));
+
+    let square: Vec<String> = ["#####", "#...#", "#...#", "#...#", "#####", "....."]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    assert_eq!(None, read_letters(&square));
+}
+
+/// The letters that can be drawn and read
+pub fn known_letters() -> Vec<char> {
+    GLYPHS.iter().map(|(c, _)| *c).collect()
+}
+
+/// Draws letters the way `read_letters` reads them, with a blank column
+/// after each one.  Returns None if there's a letter we don't know.
+pub fn draw_letters(text: &str) -> Option<Vec<String>> {
+    let mut rows = vec![String::new(); 6];
+    for c in text.chars() {
+        let (_, glyph) = GLYPHS.iter().find(|(g, _)| *g == c)?;
+        for (row, glyph_row) in rows.iter_mut().zip(glyph.iter()) {
+            row.push_str(glyph_row);
+            row.push('.');
+        }
+    }
+    Some(rows)
+}
+
+#[test]
+fn test_draw_letters() {
+    let rows = draw_letters("ZEBRA").unwrap();
+    assert_eq!(25, rows[0].len());
+    assert_eq!(Some("ZEBRA".to_string()), read_letters(&rows));
+    assert_eq!(None, draw_letters("ZEBRAS!"));
+}
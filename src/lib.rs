@@ -0,0 +1,50 @@
+//! Solutions to Advent of Code, along with the things they share, like grids
+//! and value ranges, and the machinery for running and checking them.
+//!
+//! Each year's days are in their own module, like `y2021`.
+
+pub mod answers;
+pub mod bench;
+pub mod check;
+pub mod cli;
+pub mod differential;
+pub mod generate;
+pub mod grid;
+pub mod history;
+pub mod input;
+pub mod letters;
+pub mod pathfinding;
+pub mod pool;
+pub mod random;
+pub mod scaffold;
+pub mod selector;
+pub mod sparse_grid;
+pub mod types;
+pub mod util;
+pub mod value_range;
+pub mod verify;
+pub mod watch;
+pub mod y2021;
+
+use types::Day;
+
+/// All the days of all the years, in order
+pub fn days() -> Vec<Day> {
+    [y2021::days()].concat()
+}
+
+#[test]
+fn test_days() {
+    let numbers: Vec<(usize, usize)> = days().iter().map(|d| (d.year, d.number)).collect();
+
+    // In order, with no day twice, whatever years have been added
+    assert!(numbers.windows(2).all(|w| w[0] < w[1]), "{:?}", numbers);
+
+    // All of 2021 is there
+    let days_2021: Vec<usize> = numbers
+        .iter()
+        .filter(|(year, _)| *year == 2021)
+        .map(|&(_, day)| day)
+        .collect();
+    assert_eq!((1..=25).collect::<Vec<_>>(), days_2021);
+}
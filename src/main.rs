@@ -1,130 +1,252 @@
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
 use std::env;
 use std::path::Path;
+use std::time::Duration;
 
-mod day1;
-mod day10;
-mod day11;
-mod day12;
-mod day13;
-mod day14;
-mod day15;
-mod day16;
-mod day17;
-mod day18;
-mod day19;
-mod day2;
-mod day20;
-mod day21;
-mod day22;
-mod day23;
-mod day24;
-mod day24_alu;
-mod day25;
-mod day3;
-mod day4;
-mod day5;
-mod day6;
-mod day7;
-mod day8;
-mod day9;
-mod grid;
-mod types;
-mod util;
-mod value_range;
-
-use types::{AdventResult, Answer, Day, DayPart};
-use util::lines_in_file;
-
-fn run_once(
-    day_part: &DayPart,
-    input_dir: &str,
-    file_name: &str,
-    expected: Answer,
-) -> AdventResult<Answer> {
-    let path = format!("{}/{}", input_dir, file_name);
-    let lines = lines_in_file(Path::new(&path))?;
-    let line_strs: Vec<&str> = lines.iter().map(|s| &s[..]).collect();
-    let answer = day_part.solve(&line_strs)?;
-    println!("{} -> {}", path, answer);
-    if answer != expected {
-        panic!("MISMATCH");
-    }
-    Ok(answer)
+use advent::answers::Answers;
+use advent::cli::{self, Command, Format};
+use advent::input::Input;
+use advent::types::{day_name, AdventError, AdventResult, Day, Part};
+use advent::verify::{self, RunResult};
+use advent::{bench, check, days, differential, generate, history, pool, scaffold, watch};
+
+/// Runs parts of a day against an arbitrary input, whose name is `input`.
+/// We don't know what the answers should be, so they aren't checked.
+fn run_input(
+    problems: &[(&Day, Part)],
+    input: &str,
+    input_data: &Input,
+    time_limit: Option<Duration>,
+) -> Vec<RunResult> {
+    let mut results = Vec::new();
+    for &(day, part) in problems {
+        let (actual, elapsed) = verify::solve_input(day.part(part), input_data.clone(), time_limit);
+        results.push(RunResult {
+            year: day.year,
+            day: day.number,
+            part,
+            input: input.to_string(),
+            expected: None,
+            actual,
+            elapsed,
+        });
+    }
+    results
 }
 
-fn run_day_part(day: &Day, is_first_part: bool) -> AdventResult<()> {
-    println!("\n########");
-    println!("# {} part {}", day, if is_first_part { "A" } else { "B" });
-    println!("########\n");
-    let input_dir = day.input_dir();
-    let day_part = if is_first_part {
-        &day.part_a
-    } else {
-        &day.part_b
-    };
-    run_once(day_part, &input_dir, "sample.txt", day_part.sample_answer)?;
-    run_once(day_part, &input_dir, "input.txt", day_part.full_answer)?;
-    Ok(())
+/// Prints the answers from running an arbitrary input
+fn print_input_results(results: &[RunResult], input: &str) {
+    for r in results.iter() {
+        let outcome = match &r.actual {
+            Ok(answer) => answer.to_string(),
+            Err(message) => message.clone(),
+        };
+        println!(
+            "{} part {}: {} -> {}",
+            day_name(r.year, r.day),
+            r.part,
+            input,
+            outcome
+        );
+    }
 }
 
-fn run_day(day: &Day) -> AdventResult<()> {
-    run_day_part(day, true)?;
-    run_day_part(day, false)?;
-    Ok(())
+/// Adds the results to the history file.  Not being able to doesn't make
+/// the run fail.
+fn record_history(results: &[RunResult]) {
+    if let Err(e) = history::append(Path::new(history::HISTORY_FILE), results) {
+        eprintln!("can't add to {}: {}", history::HISTORY_FILE, e);
+    }
 }
 
-fn main() -> AdventResult<()> {
-    // All the days
-    let days = vec![
-        day1::make_day_1(),
-        day2::make_day_2(),
-        day3::make_day_3(),
-        day4::make_day_4(),
-        day5::make_day_5(),
-        day6::make_day_6(),
-        day7::make_day_7(),
-        day8::make_day_8(),
-        day9::make_day_9(),
-        day10::make_day_10(),
-        day11::make_day_11(),
-        day12::make_day_12(),
-        day13::make_day_13(),
-        day14::make_day_14(),
-        day15::make_day_15(),
-        day16::make_day_16(),
-        day17::make_day_17(),
-        day18::make_day_18(),
-        day19::make_day_19(),
-        day20::make_day_20(),
-        day21::make_day_21(),
-        day22::make_day_22(),
-        day23::make_day_23(),
-        day24::make_day_24(),
-        day25::make_day_25(),
-    ];
-
-    // Parse the command-line argument to get the problem name to run, or "all"
-    let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        println!("Usage: advent [<dayNumber>|all]");
-        std::process::exit(1);
+/// Does whatever the command line says
+fn run() -> AdventResult<()> {
+    let days = days();
+
+    // Parse the command-line arguments to get the problem to run, or "all"
+    let args: Vec<String> = env::args().skip(1).collect();
+    let available: Vec<(usize, usize)> = days.iter().map(|d| (d.year, d.number)).collect();
+    let options = match cli::parse_args(&args, &available) {
+        Ok(options) => options,
+        Err(e) => {
+            println!("{}", e);
+            println!("{}", cli::USAGE);
+            std::process::exit(1);
+        }
+    };
+
+    // Making a new day doesn't run anything
+    if options.command == Command::New {
+        let selection = options.selection[0];
+        for path in scaffold::new_day(selection.year, selection.day)? {
+            println!("wrote {}", path);
+        }
+        return Ok(());
     }
 
     // Figure out which problems to run
-    let problem_name = &args[1];
-    let problems_to_run: Vec<&Day> = if problem_name == "all" {
-        days.iter().collect()
-    } else {
-        let day_number: usize = args[1].parse().unwrap();
-        vec![&days[day_number - 1]]
-    };
+    let mut problems_to_run: Vec<(&Day, Part)> = Vec::new();
+    for selection in options.selection.iter() {
+        let day = days
+            .iter()
+            .find(|d| (d.year, d.number) == (selection.year, selection.day))
+            .ok_or_else(|| {
+                AdventError::new(&format!(
+                    "no such day: {}",
+                    day_name(selection.year, selection.day)
+                ))
+            })?;
+        problems_to_run.push((day, selection.part));
+    }
+
+    // Showing the history doesn't run anything either
+    if options.command == Command::History {
+        let records = history::load(Path::new(history::HISTORY_FILE))?;
+        history::print_history(&records, &options.selection, options.threshold);
+        return Ok(());
+    }
+
+    // Checking an input only solves it if there's nothing wrong with it
+    if options.command == Command::Check {
+        let day = problems_to_run[0].0;
+        let input = options.input.as_deref().unwrap_or("-");
+        // Stdin is read once, because it can't be read twice
+        let input_data = Input::named(input)?;
+        match check::check_input(day, &input_data)? {
+            None => println!("{} has no checks for its input", day),
+            Some(problems) if problems.is_empty() => println!("{}: {} looks fine", day, input),
+            Some(problems) => {
+                for problem in problems.iter() {
+                    println!("{}", problem);
+                }
+                println!("\n{}: {} problems in {}", day, problems.len(), input);
+                std::process::exit(1);
+            }
+        }
+        let results = run_input(&problems_to_run, input, &input_data, options.time_limit);
+        record_history(&results);
+        print_input_results(&results, input);
+        if verify::any_failed(&results) {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    // Generating an input doesn't run anything
+    if options.command == Command::Gen {
+        let day = problems_to_run[0].0;
+        for line in generate::generate_input(day, options.seed, options.size)? {
+            println!("{}", line);
+        }
+        return Ok(());
+    }
+
+    // Only the parts with reference solvers can be checked against them
+    if options.command == Command::Diff {
+        problems_to_run.retain(|&(day, part)| differential::has_reference(day, part));
+        if problems_to_run.is_empty() {
+            return Err(Box::new(AdventError::new(
+                "no reference solvers for those days",
+            )));
+        }
+        let seeds = options.seed..options.seed + options.runs as u64;
+        let mut any_disagree = false;
+        for &(day, part) in problems_to_run.iter() {
+            let disagreements = differential::diff_day_part(
+                day,
+                part,
+                seeds.clone(),
+                options.size,
+                options.time_limit,
+            )?;
+            differential::print_diff(day, part, options.runs as u64, &disagreements);
+            any_disagree |= !disagreements.is_empty();
+        }
+        if any_disagree {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    // Watching runs until it's stopped
+    if options.command == Command::Watch {
+        watch::watch(&problems_to_run, options.time_limit);
+        return Ok(());
+    }
+
+    // Benchmarking runs the solvers repeatedly, and doesn't check answers
+    if options.command == Command::Bench {
+        let input = options.input.as_deref();
+        let results = bench::bench_days(&problems_to_run, input, options.runs)?;
+        bench::print_bench_summary(&results, options.runs);
+        return Ok(());
+    }
 
-    // Run them
-    for day in problems_to_run.iter() {
-        match run_day(day) {
-            Err(x) => return Err(x),
-            Ok(_) => {}
+    // An explicit input file is solved without checking the answers
+    if let Some(input) = &options.input {
+        // Stdin is read once, because it can't be read twice
+        let input_data = Input::named(input)?;
+        let results = run_input(&problems_to_run, input, &input_data, options.time_limit);
+        record_history(&results);
+        match options.format {
+            Format::Text => print_input_results(&results, input),
+            Format::Json => println!("{}", verify::results_to_json(&results)),
         }
+        if verify::any_failed(&results) {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    // Run them all, and only then report on how they did.  The parts can run
+    // at the same time, but their output is printed in order.
+    let mut answers = HashMap::new();
+    for (day, _) in problems_to_run.iter() {
+        let key = (day.year, day.number);
+        if let Entry::Vacant(e) = answers.entry(key) {
+            e.insert(Answers::load(&day.input_dir())?);
+        }
+    }
+    let work: Vec<(&Day, Part, &Answers)> = problems_to_run
+        .iter()
+        .map(|&(day, part)| (day, part, &answers[&(day.year, day.number)]))
+        .collect();
+    let mut results = Vec::new();
+    pool::run_in_order(
+        work,
+        options.jobs,
+        |(day, part, answers)| {
+            let mut out = String::new();
+            let results = verify::verify_day_part(
+                day,
+                part,
+                answers,
+                options.format,
+                options.time_limit,
+                &mut out,
+            );
+            (results, out)
+        },
+        |(day_results, out)| {
+            print!("{}", out);
+            results.extend(day_results);
+        },
+    );
+    record_history(&results);
+    match options.format {
+        Format::Text => verify::print_summary(&results),
+        Format::Json => println!("{}", verify::results_to_json(&results)),
+    }
+    if verify::any_failed(&results) {
+        std::process::exit(1);
     }
     Ok(())
 }
+
+fn main() {
+    if let Err(e) = run() {
+        eprintln!("{}", e);
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,312 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+
+use crate::grid::{GridView, Point};
+
+/// A way from one node to another: what it costs, and the nodes along the
+/// way, starting with the first one and ending with the last
+#[derive(Clone, Debug, PartialEq)]
+pub struct Path<N> {
+    pub cost: u64,
+    pub nodes: Vec<N>,
+}
+
+/// Everything a search found: each node it reached, the cheapest cost of
+/// getting there that it knows of, and the node it came from, so the path
+/// to any of them can be rebuilt.
+pub struct SearchTree<N> {
+    nodes: Vec<N>,
+    index: HashMap<N, usize>,
+    cost: Vec<u64>,
+    parent: Vec<Option<usize>>,
+}
+
+impl<N: Clone + Eq + Hash> SearchTree<N> {
+    fn new(start: N) -> SearchTree<N> {
+        let mut index = HashMap::new();
+        index.insert(start.clone(), 0);
+        SearchTree {
+            nodes: vec![start],
+            index,
+            cost: vec![0],
+            parent: vec![None],
+        }
+    }
+
+    /// The index of a node, adding it if it hasn't been reached before
+    fn add(&mut self, node: N) -> usize {
+        if let Some(&i) = self.index.get(&node) {
+            return i;
+        }
+        let i = self.nodes.len();
+        self.index.insert(node.clone(), i);
+        self.nodes.push(node);
+        self.cost.push(u64::MAX);
+        self.parent.push(None);
+        i
+    }
+
+    /// The number of nodes reached
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn cost_to(&self, node: &N) -> Option<u64> {
+        self.index.get(node).map(|&i| self.cost[i])
+    }
+
+    /// The path from the start to a node, if the search reached it
+    pub fn path_to(&self, node: &N) -> Option<Path<N>> {
+        let &end = self.index.get(node)?;
+        let mut nodes = vec![self.nodes[end].clone()];
+        let mut i = end;
+        while let Some(parent) = self.parent[i] {
+            nodes.push(self.nodes[parent].clone());
+            i = parent;
+        }
+        nodes.reverse();
+        Some(Path {
+            cost: self.cost[end],
+            nodes,
+        })
+    }
+}
+
+/// Searches in order of the cost so far plus the heuristic's guess of the
+/// cost to go, until a goal is reached, and returns what was found and
+/// which node is the goal.  Without a goal, it goes everywhere it can.
+fn best_first<N, S, I, H, G>(
+    start: N,
+    mut successors: S,
+    mut heuristic: H,
+    mut is_goal: G,
+) -> (SearchTree<N>, Option<usize>)
+where
+    N: Clone + Eq + Hash,
+    S: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, u64)>,
+    H: FnMut(&N) -> u64,
+    G: FnMut(&N) -> bool,
+{
+    let mut heap = BinaryHeap::new();
+    heap.push(Reverse((heuristic(&start), 0, 0)));
+    let mut tree = SearchTree::new(start);
+    while let Some(Reverse((_, cost, i))) = heap.pop() {
+        // A cheaper way to this node was found after this one was queued
+        if tree.cost[i] < cost {
+            continue;
+        }
+        let node = tree.nodes[i].clone();
+        if is_goal(&node) {
+            return (tree, Some(i));
+        }
+        for (next, step_cost) in successors(&node) {
+            let next_cost = cost + step_cost;
+            let j = tree.add(next);
+            if next_cost < tree.cost[j] {
+                tree.cost[j] = next_cost;
+                tree.parent[j] = Some(i);
+                heap.push(Reverse((
+                    next_cost + heuristic(&tree.nodes[j]),
+                    next_cost,
+                    j,
+                )));
+            }
+        }
+    }
+    (tree, None)
+}
+
+/// The cheapest path from the start to a goal, using Dijkstra's algorithm.
+/// `successors` gives the nodes that can be reached in one step from a
+/// node, and what each step costs.
+pub fn dijkstra<N, S, I, G>(start: N, successors: S, is_goal: G) -> Option<Path<N>>
+where
+    N: Clone + Eq + Hash,
+    S: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, u64)>,
+    G: FnMut(&N) -> bool,
+{
+    let (tree, goal) = best_first(start, successors, |_| 0, is_goal);
+    tree.path_to(&tree.nodes[goal?])
+}
+
+/// The cheapest way from the start to every node that can be reached
+pub fn dijkstra_all<N, S, I>(start: N, successors: S) -> SearchTree<N>
+where
+    N: Clone + Eq + Hash,
+    S: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, u64)>,
+{
+    best_first(start, successors, |_| 0, |_| false).0
+}
+
+/// The cheapest path from the start to a goal, using A*.  The heuristic
+/// guesses the cost from a node to the nearest goal, and the path is only
+/// the cheapest if it never guesses too high.
+pub fn a_star<N, S, I, H, G>(start: N, successors: S, heuristic: H, is_goal: G) -> Option<Path<N>>
+where
+    N: Clone + Eq + Hash,
+    S: FnMut(&N) -> I,
+    I: IntoIterator<Item = (N, u64)>,
+    H: FnMut(&N) -> u64,
+    G: FnMut(&N) -> bool,
+{
+    let (tree, goal) = best_first(start, successors, heuristic, is_goal);
+    tree.path_to(&tree.nodes[goal?])
+}
+
+/// The path from the start to a goal with the fewest steps, using a
+/// breadth-first search, for when every step costs the same
+pub fn bfs<N, S, I, G>(start: N, mut neighbors: S, mut is_goal: G) -> Option<Path<N>>
+where
+    N: Clone + Eq + Hash,
+    S: FnMut(&N) -> I,
+    I: IntoIterator<Item = N>,
+    G: FnMut(&N) -> bool,
+{
+    let mut tree = SearchTree::new(start);
+    let mut queue = VecDeque::from([0]);
+    while let Some(i) = queue.pop_front() {
+        let node = tree.nodes[i].clone();
+        if is_goal(&node) {
+            return tree.path_to(&node);
+        }
+        for next in neighbors(&node) {
+            let j = tree.add(next);
+            if tree.cost[j] == u64::MAX {
+                tree.cost[j] = tree.cost[i] + 1;
+                tree.parent[j] = Some(i);
+                queue.push_back(j);
+            }
+        }
+    }
+    None
+}
+
+/// How far apart two cells are, going only up, down, left, and right.
+/// Times the cheapest step, it's a heuristic for A* on a grid.
+pub fn manhattan_distance(a: Point, b: Point) -> u64 {
+    (a.0.abs_diff(b.0) + a.1.abs_diff(b.1)) as u64
+}
+
+/// The cheapest path through a grid, moving up, down, left, and right, where
+/// entering a cell costs what `cost_to_enter` says, or can't be done if it
+/// says None.  Grids that wrap around can be crossed at the edges.
+pub fn grid_dijkstra<G: GridView>(
+    grid: &G,
+    start: Point,
+    goal: Point,
+    cost_to_enter: impl Fn(&G::Cell) -> Option<u64>,
+) -> Option<Path<Point>> {
+    dijkstra(
+        start,
+        |&pos| {
+            grid.neigbors(pos)
+                .filter_map(|n| cost_to_enter(&grid.get(n)).map(|cost| (n, cost)))
+                .collect::<Vec<_>>()
+        },
+        |&pos| pos == goal,
+    )
+}
+
+/// The path through a grid with the fewest steps, moving up, down, left,
+/// and right, only into cells that can be entered
+pub fn grid_bfs<G: GridView>(
+    grid: &G,
+    start: Point,
+    goal: Point,
+    can_enter: impl Fn(&G::Cell) -> bool,
+) -> Option<Path<Point>> {
+    bfs(
+        start,
+        |&pos| {
+            grid.neigbors(pos)
+                .filter(|&n| can_enter(&grid.get(n)))
+                .collect::<Vec<_>>()
+        },
+        |&pos| pos == goal,
+    )
+}
+
+#[test]
+fn test_graph_search() {
+    // a -1-> b -1-> c -1-> d, and a shortcut a -5-> d that costs more
+    let edges = |node: &char| match node {
+        'a' => vec![('b', 1), ('d', 5)],
+        'b' => vec![('c', 1)],
+        'c' => vec![('d', 1)],
+        _ => vec![],
+    };
+    let path = dijkstra('a', edges, |&n| n == 'd').unwrap();
+    assert_eq!(3, path.cost);
+    assert_eq!(vec!['a', 'b', 'c', 'd'], path.nodes);
+    assert_eq!(None, dijkstra('b', edges, |&n| n == 'a'));
+
+    // Counting steps, the shortcut is better
+    let neighbors = |node: &char| edges(node).into_iter().map(|(n, _)| n);
+    let path = bfs('a', neighbors, |&n| n == 'd').unwrap();
+    assert_eq!((1, vec!['a', 'd']), (path.cost, path.nodes));
+
+    let tree = dijkstra_all('a', edges);
+    assert_eq!(4, tree.len());
+    assert_eq!(Some(2), tree.cost_to(&'c'));
+    assert_eq!(None, tree.cost_to(&'z'));
+    assert_eq!(vec!['a', 'b'], tree.path_to(&'b').unwrap().nodes);
+}
+
+#[test]
+fn test_grid_search() {
+    use crate::grid::Grid;
+
+    let grid = Grid::from_digits(&["1163", "1381", "2136"]).unwrap();
+    let path = grid_dijkstra(&grid, (0, 0), (3, 2), |&c| Some(c as u64)).unwrap();
+    assert_eq!(
+        vec![(0, 0), (0, 1), (0, 2), (1, 2), (2, 2), (3, 2)],
+        path.nodes
+    );
+    assert_eq!(1 + 2 + 1 + 3 + 6, path.cost);
+
+    // 9s are walls
+    let maze = Grid::from_digits(&["0090", "9090", "0000"]).unwrap();
+    let path = grid_bfs(&maze, (0, 0), (3, 0), |&c| c != 9).unwrap();
+    assert_eq!(7, path.cost);
+    assert_eq!(None, grid_bfs(&maze, (0, 0), (2, 0), |&c| c == 0));
+    let blocked = grid_dijkstra(&maze, (0, 0), (3, 0), |&c| (c == 0).then_some(1));
+    assert_eq!(Some(7), blocked.map(|path| path.cost));
+}
+
+#[test]
+fn test_a_star_matches_dijkstra() {
+    use crate::generate::Random;
+    use crate::grid::Grid;
+
+    let mut random = Random::new(1);
+    for _ in 0..20 {
+        let grid = Grid::from_fn((8, 8), |_| random.range(1..=9) as u8);
+        let goal = (7, 7);
+        let successors = |&pos: &Point| {
+            grid.neigbors(pos)
+                .map(|n| (n, grid[n] as u64))
+                .collect::<Vec<_>>()
+        };
+        let expected = dijkstra((0, 0), successors, |&pos| pos == goal).unwrap();
+        let path = a_star(
+            (0, 0),
+            successors,
+            |&pos| manhattan_distance(pos, goal),
+            |&pos| pos == goal,
+        )
+        .unwrap();
+        assert_eq!(expected.cost, path.cost);
+        assert_eq!(
+            path.cost,
+            path.nodes[1..].iter().map(|&n| grid[n] as u64).sum::<u64>()
+        );
+    }
+}
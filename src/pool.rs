@@ -0,0 +1,72 @@
+use std::collections::HashMap;
+use std::sync::mpsc;
+use std::sync::Mutex;
+use std::thread;
+
+/// Does `work` on each of the items, using up to `threads` threads at once,
+/// and hands the results to `report` in the same order as the items.
+///
+/// Each result is reported as soon as it and everything before it are done,
+/// so a slow item holds up the reports after it, but not the work.
+pub fn run_in_order<T, R, W, F>(items: Vec<T>, threads: usize, work: W, mut report: F)
+where
+    T: Send,
+    R: Send,
+    W: Fn(T) -> R + Sync,
+    F: FnMut(R),
+{
+    let queue = Mutex::new(items.into_iter().enumerate());
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..threads.max(1) {
+            let sender = sender.clone();
+            let queue = &queue;
+            let work = &work;
+            scope.spawn(move || loop {
+                // Take the lock only long enough to get the next item
+                let next = queue.lock().unwrap().next();
+                match next {
+                    Some((index, item)) => {
+                        if sender.send((index, work(item))).is_err() {
+                            return;
+                        }
+                    }
+                    None => return,
+                }
+            });
+        }
+
+        // The loop below ends when all of the workers are done and have
+        // dropped their senders, so this one has to go too.
+        drop(sender);
+
+        let mut waiting = HashMap::new();
+        let mut next_to_report = 0;
+        for (index, result) in receiver {
+            waiting.insert(index, result);
+            while let Some(result) = waiting.remove(&next_to_report) {
+                report(result);
+                next_to_report += 1;
+            }
+        }
+    });
+}
+
+#[test]
+fn test_run_in_order() {
+    use std::time::Duration;
+
+    // The early items are the slowest, so they finish last
+    let items: Vec<u64> = (0..8).collect();
+    let mut reported = Vec::new();
+    run_in_order(
+        items,
+        4,
+        |n| {
+            thread::sleep(Duration::from_millis(8 - n));
+            n * 10
+        },
+        |r| reported.push(r),
+    );
+    assert_eq!(vec![0, 10, 20, 30, 40, 50, 60, 70], reported);
+}
@@ -0,0 +1,70 @@
+use std::ops::RangeInclusive;
+
+/// A small, fast random number generator (SplitMix64).  It's here instead
+/// of a crate so that a seed makes the same input on every machine, forever.
+pub struct Random {
+    state: u64,
+}
+
+impl Random {
+    pub fn new(seed: u64) -> Random {
+        Random { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number from 0 up to, but not including, n
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(0 < n, "nothing to pick from");
+        (self.next_u64() % (n as u64)) as usize
+    }
+
+    /// A number in the range, including both ends
+    pub fn range(&mut self, range: RangeInclusive<i64>) -> i64 {
+        let (low, high) = range.into_inner();
+        low + (self.next_u64() % ((high - low + 1) as u64)) as i64
+    }
+
+    /// True some fraction of the time
+    pub fn chance(&mut self, fraction: f64) -> bool {
+        (self.next_u64() >> 11) as f64 / ((1u64 << 53) as f64) < fraction
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+#[test]
+fn test_random() {
+    // The same seed always gives the same numbers
+    let numbers = |seed| {
+        let mut random = Random::new(seed);
+        (0..5).map(|_| random.next_u64()).collect::<Vec<u64>>()
+    };
+    assert_eq!(numbers(7), numbers(7));
+    assert_ne!(numbers(7), numbers(8));
+    assert_eq!(0xe220a8397b1dcdaf, Random::new(0).next_u64());
+
+    let mut random = Random::new(1);
+    for _ in 0..1000 {
+        assert!(random.below(6) < 6);
+        assert!((-3..=3).contains(&random.range(-3..=3)));
+    }
+    let mut items: Vec<usize> = (0..10).collect();
+    random.shuffle(&mut items);
+    items.sort();
+    assert_eq!((0..10).collect::<Vec<usize>>(), items);
+}
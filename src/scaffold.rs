@@ -0,0 +1,378 @@
+use std::fs;
+use std::path::Path;
+
+use crate::answers::ANSWERS_FILE;
+use crate::types::{input_dir, AdventError, AdventResult};
+
+/// Where the year modules and the list of all the years live
+const LIB_PATH: &str = "src/lib.rs";
+
+/// Lines longer than this are split up, the way rustfmt does it
+const MAX_WIDTH: usize = 100;
+
+/// The starting point for a new day's module.  "{year}" and "{day}" are
+/// replaced with the year and day number.
+const DAY_TEMPLATE: &str = r#"use crate::types::{AdventError, AdventResult, Answer, Day, DayPart};
+
+fn day_{day}_a(_lines: &[&str]) -> AdventResult<Answer> {
+    Err(Box::new(AdventError::no_solution("not solved yet")))
+}
+
+#[test]
+fn test_day_{day}_a() {
+    // TODO: check the answer for the sample
+    assert!(day_{day}_a(&[]).is_err());
+}
+
+fn day_{day}_b(_lines: &[&str]) -> AdventResult<Answer> {
+    Err(Box::new(AdventError::no_solution("not solved yet")))
+}
+
+#[test]
+fn test_day_{day}_b() {
+    // TODO: check the answer for the sample
+    assert!(day_{day}_b(&[]).is_err());
+}
+
+pub fn make_day_{day}() -> Day {
+    Day::new({year}, {day}, DayPart::new(day_{day}_a), DayPart::new(day_{day}_b))
+}
+"#;
+
+/// The starting point for a day's answers file
+const ANSWERS_TEMPLATE: &str = r#"# Fill in the answers as they are found.
+
+["sample.txt"]
+# a =
+# b =
+
+["input.txt"]
+# a =
+# b =
+"#;
+
+/// The starting point for a new year's module, before it has any days
+const YEAR_TEMPLATE: &str = r#"//! The puzzles from {year}
+
+use crate::types::Day;
+
+/// All the days of {year}, in order
+pub fn days() -> Vec<Day> {
+    vec![]
+}
+"#;
+
+/// The source code for a new day's module
+pub fn day_source(year: usize, day: usize) -> String {
+    DAY_TEMPLATE
+        .replace("{year}", &year.to_string())
+        .replace("{day}", &day.to_string())
+}
+
+/// Adds a new day to the source of a year's module: the module declaration,
+/// and the call to make the day in `days()`.  Both lists are kept in order.
+pub fn add_day_to_year(year_source: &str, day: usize) -> AdventResult<String> {
+    let module = format!("day{}", day);
+    add_to_list(
+        year_source,
+        &module,
+        "pub fn days() -> Vec<Day> {",
+        ("vec![", "]"),
+        |n| format!("day{}::make_day_{}()", n, n),
+        day,
+    )
+}
+
+/// Adds a new year to the source of lib.rs: the module declaration, and
+/// its days in `days()`.  Both lists are kept in order.
+pub fn add_year_to_lib(lib_source: &str, year: usize) -> AdventResult<String> {
+    let module = format!("y{}", year);
+    add_to_list(
+        lib_source,
+        &module,
+        "pub fn days() -> Vec<Day> {",
+        ("[", "].concat()"),
+        |n| format!("y{}::days()", n),
+        year,
+    )
+}
+
+/// Adds a module declaration to a run of "pub mod" lines, and adds an item
+/// to the list that makes up the body of a function.  Items are made by
+/// `make_item` from a number, and the list is kept in order by number.
+fn add_to_list(
+    source: &str,
+    module: &str,
+    function: &str,
+    (open, close): (&str, &str),
+    make_item: impl Fn(usize) -> String,
+    number: usize,
+) -> AdventResult<String> {
+    let mod_line = format!("pub mod {};", module);
+    if source.lines().any(|line| line == mod_line) {
+        return Err(Box::new(AdventError::new(&format!(
+            "{} is already there",
+            mod_line
+        ))));
+    }
+
+    // Add the module.  If there are no modules yet, they go at the top,
+    // after any doc comments.
+    let mut lines: Vec<String> = source.lines().map(|s| s.to_string()).collect();
+    if lines.iter().any(|line| line.starts_with("pub mod ")) {
+        insert_sorted(
+            &mut lines,
+            mod_line,
+            |line| line.starts_with("pub mod "),
+            |line| line.trim_end_matches(';').to_string(),
+        )?;
+    } else {
+        let mut at = lines
+            .iter()
+            .take_while(|line| line.starts_with("//!"))
+            .count();
+        if 0 < at {
+            lines.insert(at, String::new());
+            at += 1;
+        }
+        lines.insert(at, mod_line);
+    }
+
+    // Replace the body of the function with the new list
+    let start = lines
+        .iter()
+        .position(|line| line == function)
+        .ok_or_else(|| AdventError::new(&format!("can't find {:?}", function)))?;
+    let end = start
+        + lines[start..]
+            .iter()
+            .position(|line| line == "}")
+            .ok_or_else(|| AdventError::new(&format!("can't find the end of {:?}", function)))?;
+    let body = lines[start + 1..end].join("\n");
+    let mut numbers = numbers_in(&body, &make_item);
+    numbers.push(number);
+    numbers.sort_unstable();
+    let items: Vec<String> = numbers.into_iter().map(make_item).collect();
+    let new_body = format_list(open, &items, close);
+    lines.splice(start + 1..end, new_body);
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Finds the numbers of all the items in some code
+fn numbers_in(code: &str, make_item: &impl Fn(usize) -> String) -> Vec<usize> {
+    // Every item starts the same way, up to where its number goes
+    let zero_item = make_item(0);
+    let prefix = &zero_item[..zero_item.find('0').unwrap()];
+    let mut result = Vec::new();
+    for (i, _) in code.match_indices(prefix) {
+        let digits: String = code[i + prefix.len()..]
+            .chars()
+            .take_while(|c| c.is_ascii_digit())
+            .collect();
+        if let Ok(n) = digits.parse() {
+            if code[i..].starts_with(&make_item(n)) {
+                result.push(n);
+            }
+        }
+    }
+    result
+}
+
+/// Lays out a list of items as the body of a function, on one line if it
+/// fits, and one item per line if it doesn't.
+fn format_list(open: &str, items: &[String], close: &str) -> Vec<String> {
+    let one_line = format!("    {}{}{}", open, items.join(", "), close);
+    if one_line.len() <= MAX_WIDTH {
+        return vec![one_line];
+    }
+    let mut result = vec![format!("    {}", open)];
+    for item in items {
+        result.push(format!("        {},", item));
+    }
+    match close.split_once('.') {
+        // A method call after the list goes on its own line
+        Some((bracket, method)) => {
+            result.push(format!("    {}", bracket));
+            result.push(format!("    .{}", method));
+        }
+        None => result.push(format!("    {}", close)),
+    }
+    result
+}
+
+/// Inserts a line into the run of lines that `is_member` picks out, keeping
+/// the run sorted by `key`.
+fn insert_sorted<K: Ord>(
+    lines: &mut Vec<String>,
+    new_line: String,
+    is_member: impl Fn(&str) -> bool,
+    key: impl Fn(&str) -> K,
+) -> AdventResult<()> {
+    let first = lines
+        .iter()
+        .position(|line| is_member(line))
+        .ok_or_else(|| {
+            AdventError::new(&format!("can't find where to add {:?}", new_line.trim()))
+        })?;
+    let count = lines[first..]
+        .iter()
+        .take_while(|line| is_member(line))
+        .count();
+    let new_key = key(&new_line);
+    let offset = lines[first..first + count]
+        .iter()
+        .take_while(|line| key(line) < new_key)
+        .count();
+    lines.insert(first + offset, new_line);
+    Ok(())
+}
+
+#[test]
+fn test_add_day_to_year() {
+    let one_day = "//! The puzzles from 2022
+
+pub mod day3;
+
+use crate::types::Day;
+
+/// All the days of 2022, in order
+pub fn days() -> Vec<Day> {
+    vec![day3::make_day_3()]
+}
+";
+    let two_days = "//! The puzzles from 2022
+
+pub mod day3;
+pub mod day4;
+
+use crate::types::Day;
+
+/// All the days of 2022, in order
+pub fn days() -> Vec<Day> {
+    vec![day3::make_day_3(), day4::make_day_4()]
+}
+";
+    let empty = YEAR_TEMPLATE.replace("{year}", "2022");
+    assert_eq!(one_day, add_day_to_year(&empty, 3).unwrap());
+    assert_eq!(two_days, add_day_to_year(one_day, 4).unwrap());
+    assert!(add_day_to_year(two_days, 4).is_err());
+
+    // When the list gets long, it's one day per line
+    let mut source = empty;
+    for day in [1, 2, 10, 3, 20, 4] {
+        source = add_day_to_year(&source, day).unwrap();
+    }
+    assert!(source
+        .contains("pub mod day1;\npub mod day10;\npub mod day2;\npub mod day20;\npub mod day3;\n"));
+    assert!(source.contains(
+        "    vec![
+        day1::make_day_1(),
+        day2::make_day_2(),
+        day3::make_day_3(),
+        day4::make_day_4(),
+        day10::make_day_10(),
+        day20::make_day_20(),
+    ]
+}"
+    ));
+}
+
+#[test]
+fn test_add_year_to_lib() {
+    let before = "pub mod types;
+pub mod y2021;
+
+pub fn days() -> Vec<Day> {
+    [y2021::days()].concat()
+}
+";
+    let after = "pub mod types;
+pub mod y2015;
+pub mod y2021;
+
+pub fn days() -> Vec<Day> {
+    [y2015::days(), y2021::days()].concat()
+}
+";
+    assert_eq!(after, add_year_to_lib(before, 2015).unwrap());
+
+    let years: Vec<String> = (2015..=2024).map(|y| format!("y{}::days()", y)).collect();
+    let lines = format_list("[", &years, "].concat()");
+    assert_eq!("    [", lines[0]);
+    assert_eq!("        y2015::days(),", lines[1]);
+    assert_eq!(vec!["    ]", "    .concat()"], lines[lines.len() - 2..]);
+}
+
+#[test]
+fn test_day_source() {
+    let source = day_source(2022, 7);
+    assert!(source.contains("fn day_7_a(_lines: &[&str])"));
+    assert!(source.contains("Day::new(2022, 7, DayPart::new(day_7_a), DayPart::new(day_7_b))"));
+    assert!(!source.contains("{day}"));
+    assert!(!source.contains("{year}"));
+}
+
+/// Writes a file, unless it's already there.  Returns whether it was written.
+fn create_file(path: &Path, contents: &str) -> AdventResult<bool> {
+    if path.exists() {
+        return Ok(false);
+    }
+    fs::write(path, contents)?;
+    Ok(true)
+}
+
+/// Makes everything needed to start on a new day: the module, with stubs for
+/// both parts, its place in the registry, and the input directory with
+/// empty sample and input files and an answers file.  The first day of a new year also makes
+/// the module for the year.  Returns the paths that were created or changed.
+///
+/// Paths are relative to the top of the project, like the input directories.
+pub fn new_day(year: usize, day: usize) -> AdventResult<Vec<String>> {
+    let year_dir = format!("src/y{}", year);
+    let year_path = format!("{}/mod.rs", year_dir);
+    let source_path = format!("{}/day{}.rs", year_dir, day);
+    if Path::new(&source_path).exists() {
+        return Err(Box::new(AdventError::new(&format!(
+            "{} already exists",
+            source_path
+        ))));
+    }
+
+    // Work out all the changes before writing anything
+    let is_new_year = !Path::new(&year_path).exists();
+    let (year_source, lib_source) = if is_new_year {
+        let year_source = YEAR_TEMPLATE.replace("{year}", &year.to_string());
+        let lib_source = add_year_to_lib(&fs::read_to_string(LIB_PATH)?, year)?;
+        (year_source, Some(lib_source))
+    } else {
+        (fs::read_to_string(&year_path)?, None)
+    };
+    let year_source = add_day_to_year(&year_source, day)?;
+
+    let mut changed = Vec::new();
+    fs::create_dir_all(&year_dir)?;
+    fs::write(&source_path, day_source(year, day))?;
+    changed.push(source_path);
+    fs::write(&year_path, year_source)?;
+    changed.push(year_path);
+    if let Some(lib_source) = lib_source {
+        fs::write(LIB_PATH, lib_source)?;
+        changed.push(LIB_PATH.to_string());
+    }
+
+    let input_dir = input_dir(year, day);
+    fs::create_dir_all(&input_dir)?;
+    // Every day is run on both inputs, so both have to be there
+    let files = [
+        ("sample.txt", ""),
+        ("input.txt", ""),
+        (ANSWERS_FILE, ANSWERS_TEMPLATE),
+    ];
+    for (file_name, contents) in files {
+        let path = format!("{}/{}", input_dir, file_name);
+        if create_file(Path::new(&path), contents)? {
+            changed.push(path);
+        }
+    }
+    Ok(changed)
+}
@@ -0,0 +1,198 @@
+use crate::types::{day_name, AdventError, AdventResult, Part};
+
+/// The days that have puzzles
+pub const FIRST_DAY: usize = 1;
+pub const LAST_DAY: usize = 25;
+
+/// One part of one day, picked to be run
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub struct Selection {
+    pub year: usize,
+    pub day: usize,
+    pub part: Part,
+}
+
+/// Parses a list of the days and parts to run, like "3-7", "1,5,19b",
+/// "24a", "2021/24", or "all".
+///
+/// Each comma-separated item is a day or a range of days, optionally
+/// followed by "a" or "b" to pick just one part.  A day can have its year in
+/// front, like "2021/24"; without one, it's in the latest year.  "all" is
+/// every day there is, and "2021" or "2021/all" is every day in one year.
+///
+/// Only the (year, day) pairs in `available` can be picked.  The result is
+/// sorted, with no duplicates.
+pub fn parse_selector(s: &str, available: &[(usize, usize)]) -> AdventResult<Vec<Selection>> {
+    let latest_year = available
+        .iter()
+        .map(|&(year, _)| year)
+        .max()
+        .ok_or_else(|| AdventError::new("there are no days"))?;
+    let is_year = |s: &str| {
+        s.parse::<usize>()
+            .is_ok_and(|n| available.iter().any(|&(year, _)| year == n))
+    };
+
+    let mut result = Vec::new();
+    for item in s.split(',') {
+        let (days, parts) = match item.char_indices().last() {
+            Some((i, c))
+                if c.is_ascii_alphabetic() && item[..i].ends_with(|d: char| d.is_ascii_digit()) =>
+            {
+                (&item[..i], vec![item[i..].parse::<Part>()?])
+            }
+            _ => (item, Part::both().to_vec()),
+        };
+        let (years, days) = if days == "all" {
+            (None, "all")
+        } else if is_year(days) {
+            (Some(days.parse()?), "all")
+        } else if let Some((year, days)) = days.split_once('/') {
+            if !is_year(year) {
+                return Err(Box::new(AdventError::new(&format!(
+                    "no such year: {}",
+                    year
+                ))));
+            }
+            (Some(year.parse()?), days)
+        } else {
+            (Some(latest_year), days)
+        };
+
+        let picked: Vec<(usize, usize)> = if days == "all" {
+            available
+                .iter()
+                .filter(|&&(year, _)| years.is_none_or(|y| y == year))
+                .copied()
+                .collect()
+        } else {
+            let year = years.unwrap_or(latest_year);
+            let (first, last) = match days.split_once('-') {
+                Some((first, last)) => (parse_day(first)?, parse_day(last)?),
+                None => {
+                    let day = parse_day(days)?;
+                    (day, day)
+                }
+            };
+            if last < first {
+                return Err(Box::new(AdventError::new(&format!(
+                    "backwards range of days: {}",
+                    item
+                ))));
+            }
+            for day in first..=last {
+                if !available.contains(&(year, day)) {
+                    return Err(Box::new(AdventError::new(&format!(
+                        "no such day: {}",
+                        day_name(year, day)
+                    ))));
+                }
+            }
+            (first..=last).map(|day| (year, day)).collect()
+        };
+
+        for (year, day) in picked {
+            for &part in parts.iter() {
+                result.push(Selection { year, day, part });
+            }
+        }
+    }
+    result.sort();
+    result.dedup();
+    Ok(result)
+}
+
+/// Parses one day number, checking that there is such a day
+pub fn parse_day(s: &str) -> AdventResult<usize> {
+    let day: usize = s
+        .parse()
+        .map_err(|_| AdventError::new(&format!("not a day: {:?}", s)))?;
+    if !(FIRST_DAY..=LAST_DAY).contains(&day) {
+        return Err(Box::new(AdventError::new(&format!(
+            "no such day: {} (days are {} to {})",
+            day, FIRST_DAY, LAST_DAY
+        ))));
+    }
+    Ok(day)
+}
+
+/// Every day of 2021, for tests that don't care about other years
+#[cfg(test)]
+pub(crate) fn available_2021() -> Vec<(usize, usize)> {
+    (FIRST_DAY..=LAST_DAY).map(|day| (2021, day)).collect()
+}
+
+#[test]
+fn test_parse_selector() {
+    fn sel(day: usize, part: Part) -> Selection {
+        Selection {
+            year: 2021,
+            day,
+            part,
+        }
+    }
+    use Part::{A, B};
+    let parse = |s| parse_selector(s, &available_2021());
+
+    assert_eq!(vec![sel(24, A)], parse("24a").unwrap());
+    assert_eq!(
+        vec![sel(3, A), sel(3, B), sel(4, A), sel(4, B)],
+        parse("3-4").unwrap()
+    );
+    assert_eq!(
+        vec![sel(1, A), sel(1, B), sel(5, A), sel(5, B), sel(19, B)],
+        parse("19b,5,1").unwrap()
+    );
+    assert_eq!(vec![sel(2, A), sel(2, B)], parse("2,2a").unwrap());
+    assert_eq!(50, parse("all").unwrap().len());
+    assert_eq!(vec![sel(6, B), sel(7, B)], parse("6-7b").unwrap());
+
+    assert!(parse("0").is_err());
+    assert!(parse("26").is_err());
+    assert!(parse("7-3").is_err());
+    assert!(parse("3c").is_err());
+    assert!(parse("1,,2").is_err());
+    assert!(parse("x").is_err());
+}
+
+#[test]
+fn test_parse_selector_years() {
+    fn sel(year: usize, day: usize, part: Part) -> Selection {
+        Selection { year, day, part }
+    }
+    use Part::{A, B};
+    let mut available = available_2021();
+    available.extend([(2015, 1), (2015, 2)]);
+    let parse = |s| parse_selector(s, &available);
+
+    assert_eq!(
+        vec![sel(2021, 24, A), sel(2021, 24, B)],
+        parse("2021/24").unwrap()
+    );
+    assert_eq!(vec![sel(2021, 24, B)], parse("24b").unwrap());
+    assert_eq!(
+        vec![
+            sel(2015, 1, A),
+            sel(2015, 1, B),
+            sel(2015, 2, A),
+            sel(2015, 2, B)
+        ],
+        parse("2015").unwrap()
+    );
+    assert_eq!(
+        vec![sel(2015, 1, A), sel(2015, 2, A)],
+        parse("2015/all")
+            .unwrap()
+            .into_iter()
+            .filter(|s| s.part == A)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![sel(2015, 2, B), sel(2021, 1, B)],
+        parse("2015/2b,1b").unwrap()
+    );
+    assert_eq!(54, parse("all").unwrap().len());
+
+    assert!(parse("2015/3").is_err());
+    assert!(parse("2016/1").is_err());
+}
@@ -0,0 +1,204 @@
+use std::collections::HashMap;
+
+use crate::grid::Grid;
+
+/// A point on a grid that goes on forever in every direction
+pub type SignedPoint = (i64, i64);
+
+/// An inclusive rectangle of points
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Bounds {
+    pub min: SignedPoint,
+    pub max: SignedPoint,
+}
+
+impl Bounds {
+    /// The smallest rectangle that holds all of the points, if there are any
+    pub fn around(points: impl IntoIterator<Item = SignedPoint>) -> Option<Bounds> {
+        points
+            .into_iter()
+            .fold(None, |bounds, (x, y)| match bounds {
+                None => Some(Bounds {
+                    min: (x, y),
+                    max: (x, y),
+                }),
+                Some(Bounds { min, max }) => Some(Bounds {
+                    min: (min.0.min(x), min.1.min(y)),
+                    max: (max.0.max(x), max.1.max(y)),
+                }),
+            })
+    }
+
+    pub fn width(&self) -> usize {
+        (self.max.0 - self.min.0 + 1) as usize
+    }
+
+    pub fn height(&self) -> usize {
+        (self.max.1 - self.min.1 + 1) as usize
+    }
+
+    pub fn contains(&self, pos: SignedPoint) -> bool {
+        (self.min.0..=self.max.0).contains(&pos.0) && (self.min.1..=self.max.1).contains(&pos.1)
+    }
+
+    /// The rectangle with `n` more points on every side
+    pub fn expand(&self, n: i64) -> Bounds {
+        Bounds {
+            min: (self.min.0 - n, self.min.1 - n),
+            max: (self.max.0 + n, self.max.1 + n),
+        }
+    }
+
+    /// All of the points, a row at a time from the top
+    pub fn points(&self) -> impl Iterator<Item = SignedPoint> {
+        let (min, max) = (self.min, self.max);
+        (min.1..=max.1).flat_map(move |y| (min.0..=max.0).map(move |x| (x, y)))
+    }
+}
+
+/// A grid with no edges, and negative coordinates as well as positive
+/// ones.  Every cell has the background value, except the ones that have
+/// been set to something else, which are the only ones stored.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SparseGrid<T> {
+    background: T,
+    cells: HashMap<SignedPoint, T>,
+}
+
+impl<T: Clone + PartialEq> SparseGrid<T> {
+    /// A grid that is all background
+    pub fn new(background: T) -> SparseGrid<T> {
+        SparseGrid {
+            background,
+            cells: HashMap::new(),
+        }
+    }
+
+    /// A copy of a dense grid, with its top left corner at `origin`.  Cells
+    /// with the background value aren't stored.
+    pub fn from_grid(grid: &Grid<T>, origin: SignedPoint, background: T) -> SparseGrid<T> {
+        let mut result = SparseGrid::new(background);
+        for ((x, y), value) in grid.iter_cells() {
+            result.set((origin.0 + x as i64, origin.1 + y as i64), value.clone());
+        }
+        result
+    }
+
+    pub fn background(&self) -> &T {
+        &self.background
+    }
+
+    pub fn get(&self, pos: SignedPoint) -> &T {
+        self.cells.get(&pos).unwrap_or(&self.background)
+    }
+
+    pub fn set(&mut self, pos: SignedPoint, value: T) {
+        if value == self.background {
+            self.cells.remove(&pos);
+        } else {
+            self.cells.insert(pos, value);
+        }
+    }
+
+    /// How many cells aren't background
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The cells that aren't background, in no particular order
+    pub fn iter(&self) -> impl Iterator<Item = (SignedPoint, &T)> {
+        self.cells.iter().map(|(&pos, value)| (pos, value))
+    }
+
+    /// The smallest rectangle holding every cell that isn't background,
+    /// unless they all are
+    pub fn bounds(&self) -> Option<Bounds> {
+        Bounds::around(self.cells.keys().copied())
+    }
+
+    /// A dense copy of the cells inside the bounds.  The top left corner of
+    /// the bounds is (0, 0) in the copy.
+    pub fn to_grid(&self, bounds: Bounds) -> Grid<T> {
+        Grid::from_fn((bounds.width(), bounds.height()), |(x, y)| {
+            self.get((bounds.min.0 + x as i64, bounds.min.1 + y as i64))
+                .clone()
+        })
+    }
+}
+
+#[test]
+fn test_bounds() {
+    assert_eq!(None, Bounds::around([]));
+    let bounds = Bounds::around([(3, -1), (-2, 4), (0, 0)]).unwrap();
+    assert_eq!(
+        Bounds {
+            min: (-2, -1),
+            max: (3, 4)
+        },
+        bounds
+    );
+    assert_eq!((6, 6), (bounds.width(), bounds.height()));
+    assert!(bounds.contains((-2, 4)));
+    assert!(!bounds.contains((-3, 4)));
+    assert_eq!((-3, -2), bounds.expand(1).min);
+
+    let small = Bounds {
+        min: (-1, 5),
+        max: (0, 6),
+    };
+    let points: Vec<SignedPoint> = small.points().collect();
+    assert_eq!(vec![(-1, 5), (0, 5), (-1, 6), (0, 6)], points);
+}
+
+#[test]
+fn test_sparse_grid() {
+    let mut grid = SparseGrid::new('.');
+    assert!(grid.is_empty());
+    assert_eq!(None, grid.bounds());
+    grid.set((-5, 2), '#');
+    grid.set((7, -3), '#');
+    grid.set((0, 0), '.');
+    assert_eq!(2, grid.len());
+    assert_eq!(&'#', grid.get((-5, 2)));
+    assert_eq!(&'.', grid.get((1000, -1000)));
+    assert_eq!(
+        Some(Bounds {
+            min: (-5, -3),
+            max: (7, 2)
+        }),
+        grid.bounds()
+    );
+
+    // Setting a cell back to the background forgets it
+    grid.set((7, -3), '.');
+    assert_eq!(vec![((-5, 2), &'#')], grid.iter().collect::<Vec<_>>());
+}
+
+#[test]
+fn test_sparse_dense_conversions() {
+    let dense = Grid::from_digits(&["010", "002"]).unwrap();
+    let sparse = SparseGrid::from_grid(&dense, (-1, -1), 0);
+    assert_eq!(2, sparse.len());
+    assert_eq!(&2, sparse.get((1, 0)));
+
+    let bounds = sparse.bounds().unwrap();
+    assert_eq!(
+        Bounds {
+            min: (0, -1),
+            max: (1, 0)
+        },
+        bounds
+    );
+    assert_eq!("1 0\n0 2\n", format!("{:?}", sparse.to_grid(bounds)));
+
+    // The whole thing comes back, including the background around the edges
+    let everything = Bounds {
+        min: (-1, -1),
+        max: (1, 0),
+    };
+    assert_eq!(dense, sparse.to_grid(everything));
+}
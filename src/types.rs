@@ -1,87 +1,722 @@
+use std::char::ParseCharError;
 use std::fmt::{Display, Formatter};
+use std::io::{BufRead, Cursor};
+use std::num::{ParseFloatError, ParseIntError};
+use std::str::{FromStr, ParseBoolError, Utf8Error};
+
+use num::{BigUint, ToPrimitive};
+
+use crate::input::{Input, Loaded};
+use crate::random::Random;
 
 /// Result type used throughout Advent of Code
 pub type AdventResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
 
-/// Error with a message
-#[derive(Debug, Clone)]
+/// Where in the input an error was found
+#[derive(Debug, Clone, PartialEq)]
+pub struct Location {
+    // The input file.  Solvers don't know where their input came from, so
+    // this is filled in later by whatever ran them.
+    pub file: Option<String>,
+
+    // Line numbers start at 1
+    pub line: usize,
+
+    // The line, or the part of it that was wrong
+    pub text: String,
+}
+
+impl Display for Location {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match &self.file {
+            Some(file) => write!(f, "{}:{}: {:?}", file, self.line, self.text),
+            None => write!(f, "line {}: {:?}", self.line, self.text),
+        }
+    }
+}
+
+/// The kinds of things that go wrong
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ErrorKind {
+    // Some of the input couldn't be understood
+    Parse,
+
+    // The input, or the command line, makes sense but can't be used
+    InvalidInput,
+
+    // The input is fine, but the puzzle it describes has no answer
+    NoSolution,
+
+    // Something that should never happen, like a bug in a solver
+    Internal,
+}
+
+impl Display for ErrorKind {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        let name = match self {
+            ErrorKind::Parse => "parse error",
+            ErrorKind::InvalidInput => "invalid input",
+            ErrorKind::NoSolution => "no solution",
+            ErrorKind::Internal => "internal error",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Error with a kind, a message, and where in the input it was found, if it
+/// was found in the input.
+///
+/// It may also have the error that caused it, and the context it happened
+/// in, like "scanner 7", which is added as the error is returned up the
+/// stack.
+#[derive(Debug)]
 pub struct AdventError {
+    kind: ErrorKind,
     message: String,
+    location: Option<Box<Location>>,
+
+    // Innermost first
+    context: Vec<String>,
+    source: Option<Box<dyn std::error::Error>>,
 }
 
 impl AdventError {
+    /// An error with the input, or the command line
     pub fn new(message: &str) -> AdventError {
+        AdventError::of_kind(ErrorKind::InvalidInput, message)
+    }
+
+    pub fn of_kind(kind: ErrorKind, message: &str) -> AdventError {
         AdventError {
+            kind,
             message: message.to_string(),
+            location: None,
+            context: Vec::new(),
+            source: None,
+        }
+    }
+
+    /// An error in one line of the input
+    pub fn parse(line: usize, text: &str, message: &str) -> AdventError {
+        AdventError::of_kind(ErrorKind::Parse, message).at(line, text)
+    }
+
+    pub fn no_solution(message: &str) -> AdventError {
+        AdventError::of_kind(ErrorKind::NoSolution, message)
+    }
+
+    pub fn internal(message: &str) -> AdventError {
+        AdventError::of_kind(ErrorKind::Internal, message)
+    }
+
+    /// Wraps an error from somewhere else.  Its message is the message of
+    /// this error.
+    pub fn wrap(kind: ErrorKind, source: Box<dyn std::error::Error>) -> AdventError {
+        AdventError::of_kind(kind, "").with_source(source)
+    }
+
+    /// Says what caused the error
+    pub fn with_source(mut self, source: impl Into<Box<dyn std::error::Error>>) -> AdventError {
+        self.source = Some(source.into());
+        self
+    }
+
+    /// Says what was going on when the error happened
+    pub fn context(mut self, context: &str) -> AdventError {
+        self.context.push(context.to_string());
+        self
+    }
+
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    pub fn location(&self) -> Option<&Location> {
+        self.location.as_deref()
+    }
+
+    /// Says which line of the input the error was found in.  That makes it
+    /// a parse error.
+    fn at(mut self, line: usize, text: &str) -> AdventError {
+        self.kind = ErrorKind::Parse;
+        self.on_line(line, text)
+    }
+
+    /// Says which line of the input the error is about, without changing
+    /// its kind, like a line that parses but has no solution
+    pub fn on_line(mut self, line: usize, text: &str) -> AdventError {
+        self.location = Some(Box::new(Location {
+            file: None,
+            line,
+            text: text.to_string(),
+        }));
+        self
+    }
+
+    /// Says which file the error was found in, if it was found in the input
+    pub fn in_file(mut self, file: &str) -> AdventError {
+        if let Some(location) = &mut self.location {
+            location.file = Some(file.to_string());
         }
+        self
     }
 }
 
+/// Shows the kind of error first, then the context it happened in, from
+/// the outside in, then where in the input it was, and what went wrong
 impl Display for AdventError {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
-        write!(f, "AdventError: {}", self.message)
+        let mut parts = vec![self.kind.to_string()];
+        parts.extend(self.context.iter().rev().cloned());
+        if let Some(location) = &self.location {
+            parts.push(location.to_string());
+        }
+        if !self.message.is_empty() {
+            parts.push(self.message.clone());
+        }
+        if let Some(source) = &self.source {
+            parts.push(source.to_string());
+        }
+        write!(f, "AdventError: {}", parts.join(": "))
+    }
+}
+
+impl std::error::Error for AdventError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.source.as_deref()
+    }
+}
+
+/// The kind of an error from somewhere else.  Solvers only look at their
+/// input, so not being able to parse a piece of it is a parse error, and
+/// anything else is a problem with the input.
+fn foreign_kind(error: &(dyn std::error::Error + 'static)) -> ErrorKind {
+    if error.is::<ParseIntError>()
+        || error.is::<ParseFloatError>()
+        || error.is::<ParseBoolError>()
+        || error.is::<ParseCharError>()
+        || error.is::<Utf8Error>()
+    {
+        ErrorKind::Parse
+    } else {
+        ErrorKind::InvalidInput
+    }
+}
+
+/// Turns any error into an AdventError, wrapping it if it's something else
+fn into_advent_error(error: Box<dyn std::error::Error>) -> AdventError {
+    match error.downcast::<AdventError>() {
+        Ok(e) => *e,
+        Err(e) => {
+            let kind = foreign_kind(&*e);
+            AdventError::wrap(kind, e)
+        }
+    }
+}
+
+/// Gives an error the line of input it came from, unless it already knows.
+/// Errors from elsewhere, like failing to parse a number, become parse
+/// errors, because they were found in a line of input.
+pub fn at_line(
+    error: Box<dyn std::error::Error>,
+    line: usize,
+    text: &str,
+) -> Box<dyn std::error::Error> {
+    let error = into_advent_error(error);
+    if error.location.is_some() {
+        Box::new(error)
+    } else {
+        Box::new(error.at(line, text))
+    }
+}
+
+/// Adds context to the errors in results, as they are returned
+pub trait Context<T> {
+    fn context(self, context: &str) -> AdventResult<T>;
+
+    /// Adds context that takes some work to make, only when there's an error
+    fn with_context(self, context: impl FnOnce() -> String) -> AdventResult<T>;
+}
+
+impl<T, E: Into<Box<dyn std::error::Error>>> Context<T> for Result<T, E> {
+    fn context(self, context: &str) -> AdventResult<T> {
+        self.with_context(|| context.to_string())
+    }
+
+    fn with_context(self, context: impl FnOnce() -> String) -> AdventResult<T> {
+        self.map_err(|e| {
+            let error = into_advent_error(e.into());
+            Box::new(error.context(&context())) as Box<dyn std::error::Error>
+        })
+    }
+}
+
+#[test]
+fn test_error_kinds_and_context() {
+    let error = AdventError::no_solution("no way out");
+    assert_eq!(ErrorKind::NoSolution, error.kind());
+    assert_eq!("AdventError: no solution: no way out", error.to_string());
+
+    let result: AdventResult<()> = Err(Box::new(AdventError::parse(3, "x", "bad beacon")));
+    let error = result
+        .context("scanner 7")
+        .context("day 19")
+        .unwrap_err()
+        .downcast::<AdventError>()
+        .unwrap();
+    assert_eq!(ErrorKind::Parse, error.kind());
+    assert_eq!(
+        "AdventError: parse error: day 19: scanner 7: line 3: \"x\": bad beacon",
+        error.to_string()
+    );
+
+    // Errors from elsewhere are kept as the source
+    let result: Result<u64, _> = "x".parse::<u64>();
+    let error = result.context("counting").unwrap_err();
+    assert_eq!(
+        "AdventError: parse error: counting: invalid digit found in string",
+        error.to_string()
+    );
+    let error = error.downcast::<AdventError>().unwrap();
+    assert_eq!(ErrorKind::Parse, error.kind());
+    assert!(std::error::Error::source(&*error).is_some());
+
+    // Other errors from elsewhere are a problem with the input
+    let result = u8::try_from(300);
+    let error = result.context("risk level").unwrap_err();
+    assert_eq!(
+        "AdventError: invalid input: risk level: out of range integral type conversion attempted",
+        error.to_string()
+    );
+}
+
+/// Parses each of the lines, giving any error the number and text of its
+/// line.  `first_line` is the number of the first of the lines, which is 1
+/// unless they come from the middle of the input.
+pub fn parse_lines<T>(
+    lines: &[&str],
+    first_line: usize,
+    parse: impl Fn(&str) -> AdventResult<T>,
+) -> AdventResult<Vec<T>> {
+    lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| parse(line).map_err(|e| at_line(e, first_line + i, line)))
+        .collect()
+}
+
+#[test]
+fn test_parse_errors() {
+    let parse_number = |s: &str| -> AdventResult<u64> { Ok(s.parse()?) };
+    assert_eq!(
+        vec![1, 2],
+        parse_lines(&["1", "2"], 1, parse_number).unwrap()
+    );
+
+    let error = parse_lines(&["1", "x"], 5, parse_number).unwrap_err();
+    assert_eq!(
+        "AdventError: parse error: line 6: \"x\": invalid digit found in string",
+        error.to_string()
+    );
+    let error = error
+        .downcast::<AdventError>()
+        .unwrap()
+        .in_file("input.txt");
+    assert_eq!(
+        "AdventError: parse error: input.txt:6: \"x\": invalid digit found in string",
+        error.to_string()
+    );
+
+    assert_eq!(ErrorKind::Parse, error.kind());
+
+    // The first line to find the problem is the one that's reported
+    let inner = at_line(Box::new(AdventError::new("bad")), 3, "abc");
+    assert_eq!(
+        3,
+        inner
+            .downcast_ref::<AdventError>()
+            .unwrap()
+            .location()
+            .unwrap()
+            .line
+    );
+    let outer = at_line(inner, 1, "whole input");
+    assert_eq!(
+        3,
+        outer
+            .downcast_ref::<AdventError>()
+            .unwrap()
+            .location()
+            .unwrap()
+            .line
+    );
+}
+
+/// The answer to a problem.
+///
+/// Most answers are positive integers, but some are too big for a u64, and
+/// some are text, like the letters spelled out by the dots on day 13.
+#[derive(Clone, Debug)]
+pub enum Answer {
+    Number(u64),
+    BigNumber(BigUint),
+    Text(String),
+}
+
+impl Answer {
+    /// The numeric value of the answer, if it is a number.
+    fn to_big(&self) -> Option<BigUint> {
+        match self {
+            Answer::Number(n) => Some(BigUint::from(*n)),
+            Answer::BigNumber(n) => Some(n.clone()),
+            Answer::Text(_) => None,
+        }
+    }
+}
+
+/// Numbers are equal if they have the same value, whether or not they fit in a u64.
+impl PartialEq for Answer {
+    fn eq(&self, other: &Answer) -> bool {
+        match (self, other) {
+            (Answer::Number(a), Answer::Number(b)) => a == b,
+            (Answer::Text(a), Answer::Text(b)) => a == b,
+            (Answer::Text(_), _) | (_, Answer::Text(_)) => false,
+            _ => self.to_big() == other.to_big(),
+        }
+    }
+}
+
+impl Eq for Answer {}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::BigNumber(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(n: u64) -> Answer {
+        Answer::Number(n)
     }
 }
 
-impl std::error::Error for AdventError {}
+impl From<usize> for Answer {
+    fn from(n: usize) -> Answer {
+        Answer::Number(n as u64)
+    }
+}
+
+impl From<BigUint> for Answer {
+    fn from(n: BigUint) -> Answer {
+        match n.to_u64() {
+            Some(small) => Answer::Number(small),
+            None => Answer::BigNumber(n),
+        }
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Answer {
+        Answer::Text(s.to_string())
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Answer {
+        Answer::Text(s)
+    }
+}
 
-/// The answer to each problem is a positive integer
-pub type Answer = u64;
+/// Anything made of digits is a number; everything else is text.
+impl FromStr for Answer {
+    type Err = AdventError;
+
+    fn from_str(s: &str) -> Result<Answer, AdventError> {
+        if !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()) {
+            let n: BigUint = s
+                .parse()
+                .map_err(|_| AdventError::new(&format!("bad number: {}", s)))?;
+            Ok(Answer::from(n))
+        } else {
+            Ok(Answer::Text(s.to_string()))
+        }
+    }
+}
+
+#[test]
+fn test_answer() {
+    let big: BigUint = "123456789012345678901234567890".parse().unwrap();
+    assert_eq!(Answer::Number(42), "42".parse().unwrap());
+    assert_eq!(Answer::BigNumber(big.clone()), Answer::from(big.clone()));
+    assert_eq!(
+        Answer::BigNumber(big),
+        "123456789012345678901234567890".parse().unwrap()
+    );
+    assert_eq!(
+        Answer::Text("JGAJEFKU".to_string()),
+        "JGAJEFKU".parse().unwrap()
+    );
+    assert_eq!(Answer::Number(7), Answer::BigNumber(BigUint::from(7u64)));
+    assert_ne!(Answer::Number(7), Answer::from("7"));
+    assert_eq!(
+        "18446744073709551616",
+        (Answer::from(BigUint::from(u64::MAX) + BigUint::from(1u64))).to_string()
+    );
+}
 
 /// Solutions know how to take the input lines for a problem and produce the answer.
 pub type Solver = fn(&[&str]) -> AdventResult<Answer>;
 
+/// Some solutions read their input a piece at a time instead, so that inputs
+/// too big to fit in memory can be solved.  The reader can be used for its
+/// lines, or for the raw bytes.
+pub type StreamSolver = fn(&mut dyn BufRead) -> AdventResult<Answer>;
+
+/// Checks the things a day's solvers assume about their input, which they
+/// don't check themselves.  Every problem found is returned, not just the
+/// first one.
+pub type Validator = fn(&[&str]) -> Vec<AdventError>;
+
+/// Makes a random input for a day.  What the size is depends on the day:
+/// the number of lines, the width of a grid, the number of scanners, ...
+pub type GenerateFn = fn(&mut Random, usize) -> Vec<String>;
+
+/// How to make random inputs for a day
+#[derive(Clone, Copy)]
+pub struct Generator {
+    pub generate: GenerateFn,
+
+    // A size like the size of the real inputs
+    pub default_size: usize,
+}
+
+/// Simple, slow solvers for a day, written the obvious way, to check the
+/// clever ones against.  They're only fast enough for small inputs.
+#[derive(Clone, Copy)]
+pub struct Reference {
+    pub part_a: Option<Solver>,
+    pub part_b: Option<Solver>,
+
+    // A size of generated input that the reference solvers can handle
+    pub size: usize,
+}
+
+impl Reference {
+    pub fn part(&self, part: Part) -> Option<Solver> {
+        match part {
+            Part::A => self.part_a,
+            Part::B => self.part_b,
+        }
+    }
+}
+
 /// The implementation for each day contains a solution for part A and
 /// part B of the problem.
-#[derive(Clone)]
-pub struct DayPart {
-    pub solver: Solver,
-    pub sample_answer: Answer,
-    pub full_answer: Answer,
+///
+/// The expected answers are not part of the code; they are in each day's
+/// input directory, because they depend on the input.
+#[derive(Clone, Copy)]
+pub enum DayPart {
+    Lines(Solver),
+    Stream(StreamSolver),
 }
 
 impl DayPart {
-    pub fn new(solver: Solver, sample_answer: Answer, full_answer: Answer) -> DayPart {
-        DayPart {
-            solver,
-            sample_answer,
-            full_answer,
+    pub fn new(solver: Solver) -> DayPart {
+        DayPart::Lines(solver)
+    }
+
+    pub fn streaming(solver: StreamSolver) -> DayPart {
+        DayPart::Stream(solver)
+    }
+
+    /// Reads an input the way the solver wants it.  Solvers that stream
+    /// just get a reader, and do all of their reading while they solve.
+    pub fn load(&self, input: &Input) -> std::io::Result<Loaded> {
+        match self {
+            DayPart::Lines(_) => Ok(Loaded::Lines(input.lines()?)),
+            DayPart::Stream(_) => Ok(Loaded::Reader(input.open()?)),
+        }
+    }
+
+    pub fn solve(&self, loaded: Loaded) -> AdventResult<Answer> {
+        match (self, loaded) {
+            (DayPart::Lines(solver), Loaded::Lines(lines)) => {
+                let line_refs: Vec<&str> = lines.iter().map(|s| &s[..]).collect();
+                solver(&line_refs)
+            }
+            (DayPart::Lines(solver), Loaded::Reader(reader)) => {
+                let lines: Vec<String> = reader.lines().collect::<Result<_, _>>()?;
+                let line_refs: Vec<&str> = lines.iter().map(|s| &s[..]).collect();
+                solver(&line_refs)
+            }
+            (DayPart::Stream(solver), Loaded::Reader(mut reader)) => solver(&mut reader),
+            (DayPart::Stream(solver), Loaded::Lines(lines)) => {
+                solver(&mut Cursor::new(lines.join("\n")))
+            }
         }
     }
+}
+
+#[test]
+fn test_day_part() {
+    fn count_lines(lines: &[&str]) -> AdventResult<Answer> {
+        Ok(lines.len().into())
+    }
+    fn count_bytes(reader: &mut dyn BufRead) -> AdventResult<Answer> {
+        let mut text = String::new();
+        reader.read_to_string(&mut text)?;
+        Ok(text.len().into())
+    }
+    let input = Input::text("ab\ncd");
+    for (day_part, expected) in [
+        (DayPart::new(count_lines), 2u64),
+        (DayPart::streaming(count_bytes), 5),
+    ] {
+        let loaded = day_part.load(&input).unwrap();
+        assert_eq!(Answer::from(expected), day_part.solve(loaded).unwrap());
+        let other = match day_part.load(&input).unwrap() {
+            Loaded::Lines(_) => Loaded::Reader(input.open().unwrap()),
+            Loaded::Reader(_) => Loaded::Lines(input.lines().unwrap()),
+        };
+        assert_eq!(Answer::from(expected), day_part.solve(other).unwrap());
+    }
+}
 
-    pub fn solve(&self, lines: &[&str]) -> AdventResult<Answer> {
-        let s = self.solver;
-        // convert the Vec<String> to a Vec<&str>
-        let line_refs: Vec<_> = lines.iter().map(|s| &s[..]).collect();
-        s(&line_refs[..])
+/// Which of the two parts of a day's problem
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Part {
+    A,
+    B,
+}
+
+impl Part {
+    /// Both parts, in order
+    pub fn both() -> [Part; 2] {
+        [Part::A, Part::B]
+    }
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        match self {
+            Part::A => write!(f, "A"),
+            Part::B => write!(f, "B"),
+        }
+    }
+}
+
+impl FromStr for Part {
+    type Err = AdventError;
+
+    fn from_str(s: &str) -> Result<Part, AdventError> {
+        match s {
+            "a" | "A" => Ok(Part::A),
+            "b" | "B" => Ok(Part::B),
+            _ => Err(AdventError::new(&format!("unknown part: {}", s))),
+        }
     }
 }
 
+#[test]
+fn test_parse_part() {
+    assert_eq!(Part::A, "a".parse::<Part>().unwrap());
+    assert_eq!(Part::B, "B".parse::<Part>().unwrap());
+    assert!("c".parse::<Part>().is_err());
+}
+
 /// The implementation for each day contains a solution for part A and
-/// part B of the problem.
+/// part B of the problem, and maybe ways to check its input, make random
+/// inputs, and check its answers with simpler solvers.
 #[derive(Clone)]
 pub struct Day {
+    pub year: usize,
     pub number: usize,
     pub part_a: DayPart,
     pub part_b: DayPart,
+    pub validate: Option<Validator>,
+    pub generator: Option<Generator>,
+    pub reference: Option<Reference>,
 }
 
 impl Day {
-    pub fn new(number: usize, part_a: DayPart, part_b: DayPart) -> Day {
+    pub fn new(year: usize, number: usize, part_a: DayPart, part_b: DayPart) -> Day {
         Day {
+            year,
             number,
             part_a,
             part_b,
+            validate: None,
+            generator: None,
+            reference: None,
+        }
+    }
+
+    pub fn with_validator(self, validate: Validator) -> Day {
+        Day {
+            validate: Some(validate),
+            ..self
+        }
+    }
+
+    /// Says how to make random inputs for the day, and how big they usually
+    /// are
+    pub fn with_generator(self, generate: GenerateFn, default_size: usize) -> Day {
+        Day {
+            generator: Some(Generator {
+                generate,
+                default_size,
+            }),
+            ..self
+        }
+    }
+
+    /// Adds simple, slow solvers to check the real ones against, and the
+    /// size of generated input they can handle
+    pub fn with_reference(
+        self,
+        part_a: Option<Solver>,
+        part_b: Option<Solver>,
+        size: usize,
+    ) -> Day {
+        Day {
+            reference: Some(Reference {
+                part_a,
+                part_b,
+                size,
+            }),
+            ..self
+        }
+    }
+
+    pub fn part(&self, part: Part) -> &DayPart {
+        match part {
+            Part::A => &self.part_a,
+            Part::B => &self.part_b,
         }
     }
 
     pub fn input_dir(&self) -> String {
-        format!("input/day-{}", self.number)
+        input_dir(self.year, self.number)
     }
 }
 
 impl std::fmt::Display for Day {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "day-{}", self.number)
+        write!(f, "{}", day_name(self.year, self.number))
     }
 }
+
+/// Where the inputs for a day are, like "input/2021/day-24"
+pub fn input_dir(year: usize, number: usize) -> String {
+    format!("input/{}/day-{}", year, number)
+}
+
+/// How a day is shown to people, like "2021/day-24"
+pub fn day_name(year: usize, number: usize) -> String {
+    format!("{}/day-{}", year, number)
+}
@@ -3,6 +3,7 @@
 use std::fs::File;
 use std::io::{prelude::*, BufReader};
 use std::path::Path;
+use std::time::Duration;
 
 /// Read the contents of a file as a Vec<String>
 pub fn lines_in_file(path: &Path) -> Result<Vec<String>, std::io::Error> {
@@ -10,3 +11,42 @@ pub fn lines_in_file(path: &Path) -> Result<Vec<String>, std::io::Error> {
     let lines = BufReader::new(file).lines();
     lines.collect()
 }
+
+/// Formats a duration with a unit that suits its size, like "1.25s" or "310µs"
+pub fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs_f64();
+    if 1.0 <= seconds {
+        format!("{:.2}s", seconds)
+    } else if 0.001 <= seconds {
+        format!("{:.1}ms", seconds * 1e3)
+    } else {
+        format!("{:.0}µs", seconds * 1e6)
+    }
+}
+
+#[test]
+fn test_format_duration() {
+    assert_eq!("1.25s", format_duration(Duration::from_millis(1250)));
+    assert_eq!("12.5ms", format_duration(Duration::from_micros(12500)));
+    assert_eq!("310µs", format_duration(Duration::from_micros(310)));
+}
+
+/// Prints rows of cells as a table, with each column as wide as its widest cell.
+/// The first row is the header.
+pub fn print_table(rows: &[Vec<String>]) {
+    let column_count = rows.iter().map(|row| row.len()).max().unwrap_or(0);
+    let mut widths = vec![0; column_count];
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    for row in rows {
+        let cells: Vec<String> = row
+            .iter()
+            .zip(widths.iter())
+            .map(|(cell, &width)| format!("{:width$}", cell, width = width))
+            .collect();
+        println!("{}", cells.join("  ").trim_end());
+    }
+}
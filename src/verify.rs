@@ -0,0 +1,436 @@
+use std::any::Any;
+use std::error::Error;
+use std::fmt::{self, Write};
+use std::path::PathBuf;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::answers::Answers;
+use crate::cli::Format;
+use crate::input::Input;
+#[cfg(test)]
+use crate::types::AdventResult;
+use crate::types::{day_name, AdventError, Answer, Day, DayPart, Part};
+use crate::util::{format_duration, print_table};
+
+/// The input files that are checked for every day, in the day's input directory.
+pub const INPUT_FILES: [&str; 2] = ["sample.txt", "input.txt"];
+
+/// The input files to check for a day: the usual ones, followed by any
+/// others that have expected answers.
+pub fn input_files(answers: &Answers) -> Vec<String> {
+    let mut result: Vec<String> = INPUT_FILES.iter().map(|s| s.to_string()).collect();
+    for file_name in answers.file_names() {
+        if !INPUT_FILES.contains(&file_name) {
+            result.push(file_name.to_string());
+        }
+    }
+    result
+}
+
+/// Whether a run produced the answer we expected
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Status {
+    Pass,
+    Fail,
+    Error,
+
+    // We got an answer, but don't know what it should be
+    Unknown,
+}
+
+impl Status {
+    /// The lower-case name used in machine-readable output
+    pub fn name(self) -> &'static str {
+        match self {
+            Status::Pass => "pass",
+            Status::Fail => "fail",
+            Status::Error => "error",
+            Status::Unknown => "unknown",
+        }
+    }
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Status::Pass => write!(f, "pass"),
+            Status::Fail => write!(f, "FAIL"),
+            Status::Error => write!(f, "ERROR"),
+            Status::Unknown => write!(f, "?"),
+        }
+    }
+}
+
+/// The result of running one part of one day against one input file
+#[derive(Debug)]
+pub struct RunResult {
+    pub year: usize,
+    pub day: usize,
+    pub part: Part,
+    pub input: String,
+
+    // The answer we should get, if we know it
+    pub expected: Option<Answer>,
+
+    // The answer, or the message from the error that stopped us getting one
+    pub actual: Result<Answer, String>,
+
+    // How long the solver took, not counting reading the file
+    pub elapsed: Duration,
+}
+
+impl RunResult {
+    pub fn status(&self) -> Status {
+        match (&self.actual, &self.expected) {
+            (Err(_), _) => Status::Error,
+            (Ok(_), None) => Status::Unknown,
+            (Ok(answer), Some(expected)) if answer == expected => Status::Pass,
+            (Ok(_), Some(_)) => Status::Fail,
+        }
+    }
+}
+
+#[test]
+fn test_run_result_status() {
+    fn make(expected: Option<Answer>, actual: Result<Answer, String>) -> RunResult {
+        RunResult {
+            year: 2021,
+            day: 1,
+            part: Part::A,
+            input: "sample.txt".to_string(),
+            expected,
+            actual,
+            elapsed: Duration::ZERO,
+        }
+    }
+    assert_eq!(
+        Status::Pass,
+        make(Some(7u64.into()), Ok(7u64.into())).status()
+    );
+    assert_eq!(
+        Status::Fail,
+        make(Some(7u64.into()), Ok(8u64.into())).status()
+    );
+    assert_eq!(
+        Status::Error,
+        make(Some(7u64.into()), Err("oops".to_string())).status()
+    );
+    assert_eq!(Status::Unknown, make(None, Ok(7u64.into())).status());
+}
+
+/// Solves one input, turning any error into its message.  Also returns
+/// the time taken by the solver.  For solvers that take lines, that doesn't
+/// count reading the input; solvers that stream do their reading as they go.
+///
+/// The solver runs on its own thread, so that a panic, or running for longer
+/// than the time limit, is reported as an error instead of taking down the
+/// whole run.  Threads can't be killed, so a solver that runs out of time is
+/// left running in the background.
+pub fn solve_input(
+    day_part: &DayPart,
+    input: Input,
+    time_limit: Option<Duration>,
+) -> (Result<Answer, String>, Duration) {
+    let day_part = *day_part;
+    let (sender, receiver) = mpsc::channel();
+    let start = Instant::now();
+    let handle = thread::spawn(move || {
+        let result = match day_part.load(&input) {
+            Ok(loaded) => {
+                let start = Instant::now();
+                let answer = day_part.solve(loaded).map_err(|e| error_message(e, &input));
+                (answer, start.elapsed())
+            }
+            Err(e) => (Err(format!("{}: {}", input, e)), Duration::ZERO),
+        };
+        // Nobody is listening if we ran out of time
+        let _ = sender.send(result);
+    });
+    let received = match time_limit {
+        Some(limit) => receiver.recv_timeout(limit),
+        None => receiver.recv().map_err(|_| RecvTimeoutError::Disconnected),
+    };
+    match received {
+        Ok(result) => result,
+        Err(RecvTimeoutError::Timeout) => {
+            let message = format!("timed out after {}", format_duration(start.elapsed()));
+            (Err(message), start.elapsed())
+        }
+        Err(RecvTimeoutError::Disconnected) => {
+            // The thread went away without sending an answer, so it panicked
+            let message = match handle.join() {
+                Err(payload) => format!("panicked: {}", panic_message(payload.as_ref())),
+                Ok(()) => "solver stopped without an answer".to_string(),
+            };
+            (Err(message), start.elapsed())
+        }
+    }
+}
+
+/// The message for an error from a solver.  Errors found in a line of an
+/// input file say which file.
+fn error_message(error: Box<dyn Error>, input: &Input) -> String {
+    match (error.downcast::<AdventError>(), input) {
+        (Ok(e), Input::File(path)) => e.in_file(&path.display().to_string()).to_string(),
+        (Ok(e), _) => e.to_string(),
+        (Err(e), _) => e.to_string(),
+    }
+}
+
+/// Gets the message from a panic, which is usually a String or a &str
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+#[cfg(test)]
+fn panicking_solver(_lines: &[&str]) -> AdventResult<Answer> {
+    panic!("EEK!")
+}
+
+#[cfg(test)]
+fn slow_solver(_lines: &[&str]) -> AdventResult<Answer> {
+    thread::sleep(Duration::from_secs(2));
+    Ok(1u64.into())
+}
+
+#[cfg(test)]
+fn bad_line_solver(lines: &[&str]) -> AdventResult<Answer> {
+    let numbers = crate::types::parse_lines(lines, 1, |s| Ok(s.parse::<u64>()?))?;
+    Ok(numbers.iter().sum::<u64>().into())
+}
+
+#[test]
+fn test_solve_input_errors() {
+    let path = std::env::temp_dir().join("advent-test-bad-line.txt");
+    std::fs::write(&path, "1\n2\nthree\n").unwrap();
+    let (answer, _) = solve_input(
+        &DayPart::new(bad_line_solver),
+        Input::File(path.clone()),
+        None,
+    );
+    let _ = std::fs::remove_file(&path);
+    assert_eq!(
+        Err(format!(
+            "AdventError: parse error: {}:3: \"three\": invalid digit found in string",
+            path.display()
+        )),
+        answer
+    );
+
+    let (answer, _) = solve_input(&DayPart::new(bad_line_solver), Input::text("x"), None);
+    assert_eq!(
+        Err("AdventError: parse error: line 1: \"x\": invalid digit found in string".to_string()),
+        answer
+    );
+}
+
+#[test]
+fn test_solve_lines_isolation() {
+    let (answer, _) = solve_input(&DayPart::new(panicking_solver), Input::text(""), None);
+    assert_eq!(Err("panicked: EEK!".to_string()), answer);
+
+    let limit = Some(Duration::from_millis(10));
+    let (answer, _) = solve_input(&DayPart::new(slow_solver), Input::text(""), limit);
+    assert!(answer.unwrap_err().starts_with("timed out after"));
+}
+
+/// Runs one part of a day against all of its input files, checking the
+/// answers that are known.  When the format is text, the answers are
+/// written to `out` as it goes, so that parts run at the same time don't
+/// print on top of each other.
+pub fn verify_day_part(
+    day: &Day,
+    part: Part,
+    answers: &Answers,
+    format: Format,
+    time_limit: Option<Duration>,
+    out: &mut String,
+) -> Vec<RunResult> {
+    // Writing to a String can't fail, so the results of writeln! are ignored.
+    let verbose = format == Format::Text;
+    if verbose {
+        let _ = writeln!(out, "\n########");
+        let _ = writeln!(out, "# {} part {}", day, part);
+        let _ = writeln!(out, "########\n");
+    }
+    let input_dir = day.input_dir();
+    let day_part = day.part(part);
+    let mut results = Vec::new();
+    for file_name in input_files(answers) {
+        let path = format!("{}/{}", input_dir, file_name);
+        let (actual, elapsed) =
+            solve_input(day_part, Input::File(PathBuf::from(&path)), time_limit);
+        let result = RunResult {
+            year: day.year,
+            day: day.number,
+            part,
+            expected: answers.expected(&file_name, part).cloned(),
+            input: file_name,
+            actual,
+            elapsed,
+        };
+        if verbose {
+            let _ = match &result.actual {
+                Ok(answer) => {
+                    writeln!(out, "{} -> {} ({})", path, answer, format_duration(elapsed))
+                }
+                Err(message) => writeln!(out, "{} -> {}", path, message),
+            };
+            if let (Status::Fail, Some(expected)) = (result.status(), &result.expected) {
+                let _ = writeln!(out, "MISMATCH: expected {}", expected);
+            }
+        }
+        results.push(result);
+    }
+    results
+}
+
+/// Prints a table of all the results, followed by the totals.
+pub fn print_summary(results: &[RunResult]) {
+    let header = [
+        "day", "part", "input", "expected", "actual", "time", "status",
+    ];
+    let mut rows = vec![header.iter().map(|s| s.to_string()).collect()];
+    for r in results {
+        rows.push(vec![
+            day_name(r.year, r.day),
+            r.part.to_string(),
+            r.input.clone(),
+            match &r.expected {
+                Some(expected) => expected.to_string(),
+                None => "?".to_string(),
+            },
+            match &r.actual {
+                Ok(answer) => answer.to_string(),
+                Err(message) => message.clone(),
+            },
+            format_duration(r.elapsed),
+            r.status().to_string(),
+        ]);
+    }
+
+    println!("\n########");
+    println!("# Summary");
+    println!("########\n");
+    print_table(&rows);
+
+    let count = |status| results.iter().filter(|r| r.status() == status).count();
+    let mut totals = format!(
+        "{} passed, {} failed, {} errors",
+        count(Status::Pass),
+        count(Status::Fail),
+        count(Status::Error)
+    );
+    if 0 < count(Status::Unknown) {
+        totals += &format!(", {} unknown", count(Status::Unknown));
+    }
+    println!("\n{}", totals);
+}
+
+/// Did anything produce a wrong answer, or no answer?
+pub fn any_failed(results: &[RunResult]) -> bool {
+    results
+        .iter()
+        .any(|r| matches!(r.status(), Status::Fail | Status::Error))
+}
+
+/// One result, as it appears in JSON output
+#[derive(Serialize)]
+struct JsonRecord<'a> {
+    year: usize,
+    day: usize,
+    part: String,
+    input: &'a str,
+    answer: Option<String>,
+    expected: Option<String>,
+    status: &'static str,
+    error: Option<&'a str>,
+    elapsed_ms: f64,
+}
+
+impl<'a> JsonRecord<'a> {
+    fn new(result: &'a RunResult) -> JsonRecord<'a> {
+        JsonRecord {
+            year: result.year,
+            day: result.day,
+            part: result.part.to_string(),
+            input: &result.input,
+            answer: result.actual.as_ref().ok().map(|a| a.to_string()),
+            expected: result.expected.as_ref().map(|a| a.to_string()),
+            status: result.status().name(),
+            error: result.actual.as_ref().err().map(|e| &e[..]),
+            elapsed_ms: result.elapsed.as_secs_f64() * 1e3,
+        }
+    }
+}
+
+/// Formats all of the results as a JSON array, with one record for each.
+///
+/// Answers are strings, so that big numbers survive being read into a
+/// double-precision float.
+pub fn results_to_json(results: &[RunResult]) -> String {
+    let records: Vec<JsonRecord> = results.iter().map(JsonRecord::new).collect();
+    serde_json::to_string_pretty(&records).expect("results serialize to JSON")
+}
+
+#[test]
+fn test_results_to_json() {
+    let results = [
+        RunResult {
+            year: 2021,
+            day: 2,
+            part: Part::B,
+            input: "input.txt".to_string(),
+            expected: Some(900u64.into()),
+            actual: Ok(900u64.into()),
+            elapsed: Duration::from_micros(1500),
+        },
+        RunResult {
+            year: 2021,
+            day: 3,
+            part: Part::A,
+            input: "mine.txt".to_string(),
+            expected: None,
+            actual: Err("bad \"input\"".to_string()),
+            elapsed: Duration::ZERO,
+        },
+    ];
+    let parsed: serde_json::Value = serde_json::from_str(&results_to_json(&results)).unwrap();
+    assert_eq!(
+        serde_json::json!([
+            {
+                "year": 2021,
+                "day": 2,
+                "part": "B",
+                "input": "input.txt",
+                "answer": "900",
+                "expected": "900",
+                "status": "pass",
+                "error": null,
+                "elapsed_ms": 1.5
+            },
+            {
+                "year": 2021,
+                "day": 3,
+                "part": "A",
+                "input": "mine.txt",
+                "answer": null,
+                "expected": null,
+                "status": "error",
+                "error": "bad \"input\"",
+                "elapsed_ms": 0.0
+            }
+        ]),
+        parsed
+    );
+}
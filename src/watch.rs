@@ -0,0 +1,135 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use crate::answers::Answers;
+use crate::cli::Format;
+use crate::history;
+use crate::types::{Day, Part};
+use crate::verify;
+
+/// How often to look for changes
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// When each watched file was last changed
+type Snapshot = BTreeMap<PathBuf, SystemTime>;
+
+/// Finds all of the files in the directories, and when they were changed.
+/// Directories that don't exist have no files.
+fn take_snapshot(dirs: &[String]) -> Snapshot {
+    let mut result = Snapshot::new();
+    for dir in dirs {
+        if let Ok(entries) = fs::read_dir(dir) {
+            for entry in entries.flatten() {
+                if let Ok(modified) = entry.metadata().and_then(|m| m.modified()) {
+                    result.insert(entry.path(), modified);
+                }
+            }
+        }
+    }
+    result
+}
+
+/// The files that were added, removed, or changed between two snapshots
+fn changed_files(before: &Snapshot, after: &Snapshot) -> Vec<PathBuf> {
+    let mut result: Vec<PathBuf> = after
+        .iter()
+        .filter(|(path, modified)| before.get(*path) != Some(modified))
+        .map(|(path, _)| path.clone())
+        .collect();
+    for path in before.keys() {
+        if !after.contains_key(path) {
+            result.push(path.clone());
+        }
+    }
+    result.sort();
+    result
+}
+
+#[test]
+fn test_changed_files() {
+    let t0 = SystemTime::UNIX_EPOCH;
+    let t1 = t0 + Duration::from_secs(1);
+    let path = |s: &str| PathBuf::from(s);
+    let before: Snapshot = [(path("a"), t0), (path("b"), t0), (path("c"), t0)]
+        .into_iter()
+        .collect();
+    let after: Snapshot = [(path("a"), t0), (path("b"), t1), (path("d"), t0)]
+        .into_iter()
+        .collect();
+    assert_eq!(
+        vec![path("b"), path("c"), path("d")],
+        changed_files(&before, &after)
+    );
+    assert!(changed_files(&after, &after).is_empty());
+}
+
+/// Checks the answers for the parts, prints how they did, and adds them to
+/// the history.  Problems loading the answers are printed, because the
+/// answers file may be half-edited.
+fn run_once(problems: &[(&Day, Part)], time_limit: Option<Duration>) {
+    let mut results = Vec::new();
+    for &(day, part) in problems {
+        let answers = match Answers::load(&day.input_dir()) {
+            Ok(answers) => answers,
+            Err(e) => {
+                println!("{}", e);
+                Answers::default()
+            }
+        };
+        let mut out = String::new();
+        results.extend(verify::verify_day_part(
+            day,
+            part,
+            &answers,
+            Format::Text,
+            time_limit,
+            &mut out,
+        ));
+        print!("{}", out);
+    }
+    verify::print_summary(&results);
+    if let Err(e) = history::append(Path::new(history::HISTORY_FILE), &results) {
+        eprintln!("can't add to {}: {}", history::HISTORY_FILE, e);
+    }
+}
+
+/// Checks the answers for the parts, and then again every time a file in one
+/// of their input directories changes.  Runs until the program is stopped.
+///
+/// The solvers are compiled in, so a change to a day's source file can't be
+/// picked up without rebuilding.  When that happens, we say so.
+pub fn watch(problems: &[(&Day, Part)], time_limit: Option<Duration>) {
+    let mut input_dirs: Vec<String> = problems.iter().map(|(day, _)| day.input_dir()).collect();
+    input_dirs.dedup();
+    let mut source_dirs: Vec<String> = problems
+        .iter()
+        .map(|(day, _)| format!("src/y{}", day.year))
+        .collect();
+    source_dirs.dedup();
+
+    let mut inputs = take_snapshot(&input_dirs);
+    let mut sources = take_snapshot(&source_dirs);
+    run_once(problems, time_limit);
+    println!("\nWatching {} for changes", input_dirs.join(", "));
+    loop {
+        thread::sleep(POLL_INTERVAL);
+
+        let new_sources = take_snapshot(&source_dirs);
+        for path in changed_files(&sources, &new_sources) {
+            println!("\n{} changed; rebuild to use it", path.display());
+        }
+        sources = new_sources;
+
+        let new_inputs = take_snapshot(&input_dirs);
+        let changed = changed_files(&inputs, &new_inputs);
+        if !changed.is_empty() {
+            let names: Vec<String> = changed.iter().map(|p| p.display().to_string()).collect();
+            println!("\n######## changed: {}", names.join(", "));
+            run_once(problems, time_limit);
+        }
+        inputs = new_inputs;
+    }
+}
@@ -0,0 +1,89 @@
+use std::io::BufRead;
+
+use crate::generate::Random;
+use crate::types::{at_line, AdventResult, Answer, Day, DayPart};
+
+/// Reads lines of input as u64, one at a time
+fn lines_to_numbers(input: &mut dyn BufRead) -> impl Iterator<Item = AdventResult<u64>> + '_ {
+    input.lines().enumerate().map(|(i, line)| {
+        let line = line?;
+        line.parse().map_err(|e| at_line(Box::new(e), i + 1, &line))
+    })
+}
+
+#[test]
+fn test_lines_to_numbers() {
+    let numbers: AdventResult<Vec<u64>> = lines_to_numbers(&mut "1\n456\n".as_bytes()).collect();
+    assert_eq!(vec![1, 456], numbers.unwrap());
+    let error = lines_to_numbers(&mut "1\nx".as_bytes())
+        .nth(1)
+        .unwrap()
+        .unwrap_err();
+    assert_eq!(
+        "AdventError: parse error: line 2: \"x\": invalid digit found in string",
+        error.to_string()
+    );
+}
+
+/// 1a: Counts lines containin numbers bigger than the line before
+fn day_1_a(input: &mut dyn BufRead) -> AdventResult<Answer> {
+    let mut prev: Option<u64> = None;
+    let mut count: u64 = 0;
+    for value in lines_to_numbers(input) {
+        let value = value?;
+        let is_increase = match prev {
+            Some(prev_value) => prev_value < value,
+            None => false,
+        };
+        if is_increase {
+            count += 1;
+        }
+        prev = Some(value)
+    }
+    Ok(count.into())
+}
+
+/// 1b: Counts groups of three lines containin numbers bigger than the line before
+fn day_1_b(input: &mut dyn BufRead) -> AdventResult<Answer> {
+    let mut a;
+    let mut b: u64 = 0;
+    let mut c: u64 = 0;
+    let mut num_seen: u64 = 0;
+    let mut prev_sum: u64 = 0;
+    let mut count: u64 = 0;
+    for value in lines_to_numbers(input) {
+        a = b;
+        b = c;
+        c = value?;
+        num_seen += 1;
+        if 3 <= num_seen {
+            let sum = a + b + c;
+            if 4 <= num_seen && prev_sum < sum {
+                count += 1;
+            }
+            prev_sum = sum;
+        }
+    }
+    Ok(count.into())
+}
+
+/// Depths that mostly get deeper, like the real ones
+fn generate(random: &mut Random, size: usize) -> Vec<String> {
+    let mut depth = random.range(100..=200);
+    (0..size)
+        .map(|_| {
+            depth = (depth + random.range(-10..=20)).max(0);
+            depth.to_string()
+        })
+        .collect()
+}
+
+pub fn make_day_1() -> Day {
+    Day::new(
+        2021,
+        1,
+        DayPart::streaming(day_1_a),
+        DayPart::streaming(day_1_b),
+    )
+    .with_generator(generate, 2000)
+}
@@ -0,0 +1,167 @@
+use crate::generate::Random;
+use crate::types::{parse_lines, AdventError, AdventResult, Answer, Day, DayPart};
+
+#[derive(Debug, PartialEq)]
+enum Char {
+    Open(u8),
+    Close(u8),
+}
+
+fn parse_char(c: u8) -> Result<Char, AdventError> {
+    match c {
+        b'(' => Ok(Char::Open(b'(')),
+        b')' => Ok(Char::Close(b'(')),
+        b'[' => Ok(Char::Open(b'[')),
+        b']' => Ok(Char::Close(b'[')),
+        b'{' => Ok(Char::Open(b'{')),
+        b'}' => Ok(Char::Close(b'{')),
+        b'<' => Ok(Char::Open(b'<')),
+        b'>' => Ok(Char::Close(b'<')),
+        _ => Err(AdventError::new(&format!("bad char: {:?}", c as char))),
+    }
+}
+
+/// The result of checking a line.
+#[derive(Debug, PartialEq)]
+enum LineStatus {
+    // There was a mismatch; holds score of the mismatch
+    Mismatch(u64),
+
+    // Everything was fine; holds the score of un-closed opening chars
+    Incomplete(u64),
+}
+
+fn score_for_char(b: u8) -> u64 {
+    match b {
+        b'(' => 3,
+        b'[' => 57,
+        b'{' => 1197,
+        b'<' => 25137,
+        _ => {
+            panic!("Unknown closing char: {:?}", b);
+        }
+    }
+}
+
+/// Compute the score for an incomplete line
+fn score_incomplete(stack: &Vec<u8>) -> u64 {
+    let mut result = 0;
+    for b in stack.iter().rev() {
+        let char_score = match b {
+            b'(' => 1,
+            b'[' => 2,
+            b'{' => 3,
+            b'<' => 4,
+            _ => panic!("Unknown incomplete char: {:?}", b),
+        };
+        result = result * 5 + char_score;
+    }
+    result
+}
+
+/// Check a line and return its status
+fn check_line(line: &str) -> Result<LineStatus, AdventError> {
+    let mut stack: Vec<u8> = Vec::new();
+    for c in line.bytes().map(parse_char) {
+        match c? {
+            Char::Open(b) => {
+                stack.push(b);
+            }
+            Char::Close(b) => match stack.last() {
+                Some(&open) if open == b => {
+                    stack.pop();
+                }
+                Some(_) => return Ok(LineStatus::Mismatch(score_for_char(b))),
+                None => return Err(AdventError::new("closing a chunk that isn't open")),
+            },
+        }
+    }
+    Ok(LineStatus::Incomplete(score_incomplete(&stack)))
+}
+
+#[test]
+fn test_check_line() {
+    assert_eq!(
+        LineStatus::Mismatch(1197),
+        check_line("{([(<{}[<>[]}>{[]{[(<()>").unwrap()
+    );
+    assert_eq!(
+        LineStatus::Mismatch(3),
+        check_line("[[<[([]))<([[{}[[()]]]").unwrap()
+    );
+    assert_eq!(
+        LineStatus::Incomplete(288957),
+        check_line("[({(<(())[]>[[{[]{<()<>>").unwrap()
+    );
+    assert_eq!(
+        LineStatus::Incomplete(5566),
+        check_line("[(()[<>])]({[<{<<[]>>(").unwrap()
+    );
+    assert!(check_line("[(x)]").is_err());
+    assert!(check_line("[])").is_err());
+}
+
+fn parse_input(lines: &[&str]) -> AdventResult<Vec<LineStatus>> {
+    parse_lines(lines, 1, |line| Ok(check_line(line)?))
+}
+
+fn day_10_a(lines: &[&str]) -> AdventResult<Answer> {
+    let answer: u64 = parse_input(lines)?
+        .iter()
+        .filter_map(|status| match status {
+            LineStatus::Mismatch(score) => Some(*score),
+            LineStatus::Incomplete(_) => None,
+        })
+        .sum();
+    Ok(answer.into())
+}
+
+fn day_10_b(lines: &[&str]) -> AdventResult<Answer> {
+    let mut answers: Vec<u64> = parse_input(lines)?
+        .iter()
+        .filter_map(|status| match status {
+            LineStatus::Mismatch(_) => None,
+            LineStatus::Incomplete(score) => Some(*score),
+        })
+        .collect();
+    if answers.is_empty() {
+        return Err(Box::new(AdventError::new("no incomplete lines")));
+    }
+    answers.sort();
+    Ok(answers[answers.len() / 2].into())
+}
+
+/// Lines of chunks that are either corrupted or incomplete
+fn generate(random: &mut Random, size: usize) -> Vec<String> {
+    let pairs = [('(', ')'), ('[', ']'), ('{', '}'), ('<', '>')];
+    (0..size)
+        .map(|_| {
+            let length = random.range(40..=110) as usize;
+            let mut line = String::new();
+            let mut open: Vec<usize> = Vec::new();
+            while line.len() < length || open.is_empty() {
+                if open.is_empty() || (open.len() < 12 && random.chance(0.55)) {
+                    let kind = random.below(4);
+                    open.push(kind);
+                    line.push(pairs[kind].0);
+                } else {
+                    line.push(pairs[open.pop().unwrap()].1);
+                }
+            }
+            if random.chance(0.5) {
+                // Close a chunk with the wrong character, and keep going
+                let expected = *open.last().unwrap();
+                let wrong = (expected + 1 + random.below(3)) % 4;
+                line.push(pairs[wrong].1);
+                for _ in 0..random.below(10) {
+                    line.push(pairs[random.below(4)].0);
+                }
+            }
+            line
+        })
+        .collect()
+}
+
+pub fn make_day_10() -> Day {
+    Day::new(2021, 10, DayPart::new(day_10_a), DayPart::new(day_10_b)).with_generator(generate, 100)
+}
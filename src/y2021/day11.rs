@@ -1,3 +1,4 @@
+use crate::generate::Random;
 use crate::grid::{parse_grid, Grid, Point};
 use crate::types::{AdventResult, Answer, Day, DayPart};
 
@@ -5,7 +6,7 @@ use crate::types::{AdventResult, Answer, Day, DayPart};
 /// and doing the same to neighbors if this one incremented
 /// to 9.  Returns the number of flashes, including ones
 /// we trigger in neighbors.
-fn increment_one(grid: &mut Grid, pos: Point) -> Answer {
+fn increment_one(grid: &mut Grid, pos: Point) -> u64 {
     let new_value = grid.get(pos) + 1;
     grid.set(pos, new_value);
     let mut flash_count = 0;
@@ -20,9 +21,9 @@ fn increment_one(grid: &mut Grid, pos: Point) -> Answer {
 
 #[test]
 fn test_increment_one() {
-    let mut grid = parse_grid(&["111", "199", "191", "111"]);
+    let mut grid = parse_grid(&["111", "199", "191", "111"]).unwrap();
     assert_eq!(3, increment_one(&mut grid, (1, 1)));
-    let mut expected = parse_grid(&["233", "300", "304", "222"]);
+    let mut expected = parse_grid(&["233", "300", "304", "222"]).unwrap();
     expected.set((1, 1), 12);
     expected.set((2, 1), 11);
     expected.set((1, 2), 11);
@@ -31,7 +32,7 @@ fn test_increment_one() {
 
 /// Takes the entire grid to the next step, returning
 /// the number of flashes that happened.
-fn one_step(grid: &mut Grid) -> Answer {
+fn one_step(grid: &mut Grid) -> u64 {
     let (width, height) = grid.shape();
     let mut flash_count = 0;
     for x in 0..width {
@@ -51,38 +52,53 @@ fn one_step(grid: &mut Grid) -> Answer {
 
 #[test]
 fn test_one_step() {
-    let mut grid = parse_grid(&vec!["11111", "19991", "19191", "19991", "11111"]);
+    let mut grid = parse_grid(&vec!["11111", "19991", "19191", "19991", "11111"]).unwrap();
     assert_eq!(9, one_step(&mut grid));
-    let expected = parse_grid(&vec!["34543", "40004", "50005", "40004", "34543"]);
+    let expected = parse_grid(&vec!["34543", "40004", "50005", "40004", "34543"]).unwrap();
     assert_eq!(expected, grid);
 }
 
 fn day_11_a(lines: &[&str]) -> AdventResult<Answer> {
-    let mut grid = parse_grid(lines);
+    let mut grid = parse_grid(lines)?;
     let mut flash_count = 0;
     for _ in 0..100 {
         flash_count += one_step(&mut grid);
     }
-    Ok(flash_count)
+    Ok(flash_count.into())
 }
 
 fn day_11_b(lines: &[&str]) -> AdventResult<Answer> {
-    let mut grid = parse_grid(lines);
+    let mut grid = parse_grid(lines)?;
     let (width, height) = grid.shape();
-    let octopus_count = (width * height) as Answer;
-    let mut step_count = 0;
+    let octopus_count = (width * height) as u64;
+    let mut step_count: u64 = 0;
     loop {
         step_count += 1;
         if one_step(&mut grid) == octopus_count {
-            return Ok(step_count);
+            return Ok(step_count.into());
+        }
+    }
+}
+
+/// A square of octopuses that all end up flashing at the same time.  Not
+/// every arrangement does that, so random ones are tried until one does.
+fn generate(random: &mut Random, size: usize) -> Vec<String> {
+    loop {
+        let lines: Vec<String> = (0..size)
+            .map(|_| {
+                (0..size)
+                    .map(|_| (b'0' + random.below(10) as u8) as char)
+                    .collect()
+            })
+            .collect();
+        let strs: Vec<&str> = lines.iter().map(|s| &s[..]).collect();
+        let mut grid = parse_grid(&strs).unwrap();
+        if (0..10_000).any(|_| one_step(&mut grid) == (size * size) as u64) {
+            return lines;
         }
     }
 }
 
 pub fn make_day_11() -> Day {
-    Day::new(
-        11,
-        DayPart::new(day_11_a, 1656, 1617),
-        DayPart::new(day_11_b, 195, 258),
-    )
+    Day::new(2021, 11, DayPart::new(day_11_a), DayPart::new(day_11_b)).with_generator(generate, 10)
 }
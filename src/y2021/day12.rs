@@ -1,6 +1,7 @@
 use std::collections::{HashMap, HashSet};
 
-use crate::types::{AdventResult, Answer, Day, DayPart};
+use crate::generate::Random;
+use crate::types::{AdventError, AdventResult, Answer, Day, DayPart};
 
 /// A graph is represented as a mapping from a node to the set of
 /// nodes you can reach from it.
@@ -41,22 +42,26 @@ impl<'a, 'b> Graph<'a> {
     }
 }
 
-fn parse_graph<'a>(lines: &'a [&str]) -> Graph<'a> {
+fn parse_graph<'a>(lines: &'a [&str]) -> AdventResult<Graph<'a>> {
     let mut graph = Graph::empty();
-    for line in lines {
+    for (i, line) in lines.iter().enumerate() {
         let parts: Vec<_> = line.split("-").collect();
-        if parts.len() != 2 {
-            panic!("Bad graph line: {:?}", line);
+        if parts.len() != 2 || parts.iter().any(|room| room.is_empty()) {
+            return Err(Box::new(AdventError::parse(
+                i + 1,
+                line,
+                "Bad graph line; expected two rooms, like start-A",
+            )));
         }
         graph.add_undirected_edge(parts[0], parts[1]);
     }
-    graph
+    Ok(graph)
 }
 
 #[test]
 fn test_parse_graph() {
     let lines = ["a-b", "c-b"];
-    let graph = parse_graph(&lines);
+    let graph = parse_graph(&lines).unwrap();
 
     fn make_set<'a>(items: &[&'a str]) -> HashSet<&'a str> {
         items.iter().map(|&n| n).collect()
@@ -65,6 +70,10 @@ fn test_parse_graph() {
     assert_eq!(&make_set(&["b"]), graph.neighbors("a"));
     assert_eq!(&make_set(&["a", "c"]), graph.neighbors("b"));
     assert_eq!(&make_set(&["b"]), graph.neighbors("c"));
+
+    assert!(parse_graph(&["a-b", "c"]).is_err());
+    assert!(parse_graph(&["a-b-c"]).is_err());
+    assert!(parse_graph(&["a-"]).is_err());
 }
 
 /// Is the room big?
@@ -84,7 +93,7 @@ fn paths_to_end<'a, 'b>(
     stack: &'b mut Vec<&'a str>,
     have_repeated: bool,
     can_visit: CanVisitFn,
-) -> Answer {
+) -> u64 {
     if current == "end" {
         1
     } else {
@@ -102,19 +111,20 @@ fn paths_to_end<'a, 'b>(
 }
 
 fn day_12_a(lines: &[&str]) -> AdventResult<Answer> {
-    let graph = parse_graph(lines);
-    Ok(paths_to_end(
+    let graph = parse_graph(lines)?;
+    let count = paths_to_end(
         &graph,
         "start",
         &mut vec!["start"],
         false,
         |node, stack, _| is_big(node) || !stack.contains(&node),
-    ))
+    );
+    Ok(count.into())
 }
 
 fn day_12_b(lines: &[&str]) -> AdventResult<Answer> {
-    let graph = parse_graph(lines);
-    Ok(paths_to_end(
+    let graph = parse_graph(lines)?;
+    let count = paths_to_end(
         &graph,
         "start",
         &mut vec!["start"],
@@ -122,13 +132,54 @@ fn day_12_b(lines: &[&str]) -> AdventResult<Answer> {
         |node, stack, have_repeated| {
             node != "start" && (is_big(node) || !have_repeated || !stack.contains(&node))
         },
-    ))
+    );
+    Ok(count.into())
+}
+
+/// A map of caves where big caves are never next to each other, so the
+/// number of paths is finite.  Every cave can be reached from the start.
+fn generate(random: &mut Random, size: usize) -> Vec<String> {
+    let name = |random: &mut Random, big: bool| -> String {
+        let base = if big { b'A' } else { b'a' };
+        (0..2)
+            .map(|_| (base + random.below(26) as u8) as char)
+            .collect()
+    };
+    let mut caves = vec![("start".to_string(), false), ("end".to_string(), false)];
+    while caves.len() < 2 + (size / 3).max(2) {
+        let big = caves.len() % 3 == 0;
+        let cave = name(random, big);
+        if !caves.iter().any(|(c, _)| *c == cave) {
+            caves.push((cave, big));
+        }
+    }
+
+    // Each cave is connected to one before it, so they're all connected.
+    // Then there are more tunnels, for more ways through.
+    let mut tunnels: Vec<(usize, usize)> = Vec::new();
+    for i in 1..caves.len() {
+        let mut j = random.below(i);
+        if caves[i].1 && caves[j].1 {
+            j = 0;
+        }
+        tunnels.push((j, i));
+    }
+    let mut tries = 0;
+    while tunnels.len() < size && tries < 100 * size {
+        tries += 1;
+        let (a, b) = (random.below(caves.len()), random.below(caves.len()));
+        let both_big = caves[a].1 && caves[b].1;
+        if a != b && !both_big && !tunnels.contains(&(a, b)) && !tunnels.contains(&(b, a)) {
+            tunnels.push((a, b));
+        }
+    }
+    random.shuffle(&mut tunnels);
+    tunnels
+        .iter()
+        .map(|&(a, b)| format!("{}-{}", caves[a].0, caves[b].0))
+        .collect()
 }
 
 pub fn make_day_12() -> Day {
-    Day::new(
-        12,
-        DayPart::new(day_12_a, 10, 4792),
-        DayPart::new(day_12_b, 36, 133360),
-    )
+    Day::new(2021, 12, DayPart::new(day_12_a), DayPart::new(day_12_b)).with_generator(generate, 23)
 }
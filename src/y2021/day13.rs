@@ -0,0 +1,229 @@
+use std::str::FromStr;
+
+use crate::generate::Random;
+use crate::grid::parse_point;
+use crate::letters::{draw_letters, known_letters, read_letters};
+use crate::sparse_grid::{Bounds, SignedPoint, SparseGrid};
+use crate::types::{parse_lines, AdventError, AdventResult, Answer, Day, DayPart};
+use lazy_static::lazy_static;
+use regex::Regex;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum FoldInstruction {
+    X(i64),
+    Y(i64),
+}
+
+lazy_static! {
+    static ref FOLD_PATTERN: Regex =
+        Regex::new(r"fold along ([x|y])=([0-9]+)").expect("fold regex");
+}
+
+impl FromStr for FoldInstruction {
+    type Err = AdventError;
+
+    fn from_str(s: &str) -> Result<FoldInstruction, AdventError> {
+        match FOLD_PATTERN.captures(s) {
+            None => Err(AdventError::new("bad fold instruction")),
+            Some(captures) => {
+                let axis = &captures[1];
+                let ordinate: i64 = captures[2]
+                    .parse()
+                    .map_err(|_| AdventError::new("fold line is too big"))?;
+                match axis {
+                    "x" => Ok(FoldInstruction::X(ordinate)),
+                    "y" => Ok(FoldInstruction::Y(ordinate)),
+                    _ => Err(AdventError::internal("bug in fold regex")),
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_parse_fold_instruction() {
+    assert_eq!(
+        FoldInstruction::X(10),
+        FoldInstruction::from_str("fold along x=10").unwrap()
+    );
+    assert!(FoldInstruction::from_str("fold along z=10").is_err());
+}
+
+/// Returns the new location of a point after folding.  Points more than
+/// twice as far from the edge as the fold end up past the edge.
+fn fold_point(p: SignedPoint, fold: FoldInstruction) -> SignedPoint {
+    match fold {
+        FoldInstruction::X(n) => {
+            if p.0 < n {
+                p
+            } else {
+                (2 * n - p.0, p.1)
+            }
+        }
+        FoldInstruction::Y(n) => {
+            if p.1 < n {
+                p
+            } else {
+                (p.0, 2 * n - p.1)
+            }
+        }
+    }
+}
+
+#[test]
+fn test_fold_point() {
+    assert_eq!((1, 3), fold_point((1, 3), FoldInstruction::X(5)));
+    assert_eq!((1, 3), fold_point((9, 3), FoldInstruction::X(5)));
+    assert_eq!((3, 1), fold_point((3, 1), FoldInstruction::Y(5)));
+    assert_eq!((3, 1), fold_point((3, 9), FoldInstruction::Y(5)));
+    assert_eq!((-2, 1), fold_point((12, 1), FoldInstruction::X(5)));
+}
+
+/// The dots on the paper are true, and everywhere else is false
+type Paper = SparseGrid<bool>;
+
+#[derive(Debug)]
+struct Input {
+    paper: Paper,
+    folds: Vec<FoldInstruction>,
+}
+
+/// The input is the points, then a blank line, then the folds
+fn parse_input(lines: &[&str]) -> AdventResult<Input> {
+    let point_count = lines.iter().take_while(|&line| *line != "").count();
+    let points = parse_lines(&lines[..point_count], 1, parse_point)?;
+    let fold_lines = lines.get(point_count + 1..).unwrap_or(&[]);
+    let folds = parse_lines(fold_lines, point_count + 2, |line| {
+        Ok(FoldInstruction::from_str(line)?)
+    })?;
+    if points.is_empty() || folds.is_empty() {
+        return Err(Box::new(AdventError::new(
+            "expected points, a blank line, and folds",
+        )));
+    }
+    let mut paper = Paper::new(false);
+    for (x, y) in points {
+        paper.set((x as i64, y as i64), true);
+    }
+    Ok(Input { paper, folds })
+}
+
+#[test]
+fn test_parse_input() {
+    let input = parse_input(&["1,2", "3,4", "", "fold along y=7"]).unwrap();
+    assert_eq!(2, input.paper.len());
+    assert_eq!(vec![FoldInstruction::Y(7)], input.folds);
+
+    let error = parse_input(&["1,2", "", "fold along y=7", "fold x=3"]).unwrap_err();
+    assert_eq!(
+        "AdventError: parse error: line 4: \"fold x=3\": bad fold instruction",
+        error.to_string()
+    );
+    assert!(parse_input(&["1,2", "3;4", "", "fold along y=7"]).is_err());
+    assert!(parse_input(&["1,2"]).is_err());
+}
+
+fn fold(paper: &Paper, f: FoldInstruction) -> Paper {
+    let mut result = Paper::new(false);
+    for (p, _) in paper.iter() {
+        result.set(fold_point(p, f), true);
+    }
+    result
+}
+
+fn day_13_a(lines: &[&str]) -> AdventResult<Answer> {
+    let input = parse_input(lines)?;
+    let paper = fold(&input.paper, input.folds[0]);
+    Ok(paper.len().into())
+}
+
+/// Draws the dots as rows of '#' and '.', starting from the top left
+/// corner of the paper, or from any dots that ended up past it
+fn point_grid_rows(paper: &Paper) -> Vec<String> {
+    let dots = paper.bounds().unwrap();
+    let bounds = Bounds {
+        min: (dots.min.0.min(0), dots.min.1.min(0)),
+        max: dots.max,
+    };
+    let grid = paper.to_grid(bounds);
+    let (columns, rows) = grid.shape();
+    (0..rows)
+        .map(|y| {
+            (0..columns)
+                .map(|x| if grid[(x, y)] { '#' } else { '.' })
+                .collect()
+        })
+        .collect()
+}
+
+fn day_13_b(lines: &[&str]) -> AdventResult<Answer> {
+    // Get the input
+    let input = parse_input(lines)?;
+
+    // Execute all of the folding instructions
+    let mut paper = input.paper;
+    for f in input.folds {
+        paper = fold(&paper, f);
+    }
+
+    // Read the letters.  If it's not letters, the answer is the picture.
+    let rows = point_grid_rows(&paper);
+    match read_letters(&rows) {
+        Some(letters) => Ok(Answer::from(letters)),
+        None => Ok(Answer::from(rows.join("/"))),
+    }
+}
+
+/// Random letters, unfolded.  Going backwards from the letters, each fold
+/// doubles the paper, and each dot ends up on one side of the fold, or the
+/// other, or both.
+fn generate(random: &mut Random, size: usize) -> Vec<String> {
+    let letters = known_letters();
+    let text: String = (0..8).map(|_| *random.choose(&letters)).collect();
+    let mut points: Vec<(usize, usize)> = Vec::new();
+    for (y, row) in draw_letters(&text).unwrap().iter().enumerate() {
+        for (x, c) in row.chars().enumerate() {
+            if c == '#' {
+                points.push((x, y));
+            }
+        }
+    }
+
+    let (mut width, mut height) = (40, 6);
+    let mut folds = Vec::new();
+    for i in 0..size {
+        let along_x = i % 2 == 0;
+        let line = if along_x { width } else { height };
+        let mut unfolded = Vec::new();
+        for &(x, y) in points.iter() {
+            let mirrored = if along_x {
+                (2 * line - x, y)
+            } else {
+                (x, 2 * line - y)
+            };
+            match random.below(3) {
+                0 => unfolded.push((x, y)),
+                1 => unfolded.push(mirrored),
+                _ => unfolded.extend([(x, y), mirrored]),
+            }
+        }
+        points = unfolded;
+        if along_x {
+            width = 2 * width + 1;
+            folds.push(format!("fold along x={}", line));
+        } else {
+            height = 2 * height + 1;
+            folds.push(format!("fold along y={}", line));
+        }
+    }
+    random.shuffle(&mut points);
+
+    let mut result: Vec<String> = points.iter().map(|(x, y)| format!("{},{}", x, y)).collect();
+    result.push("".to_string());
+    result.extend(folds.into_iter().rev());
+    result
+}
+
+pub fn make_day_13() -> Day {
+    Day::new(2021, 13, DayPart::new(day_13_a), DayPart::new(day_13_b)).with_generator(generate, 12)
+}
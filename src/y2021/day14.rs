@@ -4,7 +4,8 @@ use itertools::Itertools;
 use lazy_static::lazy_static;
 use regex::Regex;
 
-use crate::types::{AdventResult, Answer, Day, DayPart};
+use crate::generate::Random;
+use crate::types::{at_line, parse_lines, AdventError, AdventResult, Answer, Day, DayPart};
 
 /// A pair of two adjacent letters
 type Pair = (char, char);
@@ -34,16 +35,19 @@ struct State {
 }
 
 /// Parses a string of chars and turns it into a State.
-fn parse_state(line: &str) -> State {
-    let first_char = line.chars().next().unwrap();
+fn parse_state(line: &str) -> Result<State, AdventError> {
+    let first_char = line
+        .chars()
+        .next()
+        .ok_or_else(|| AdventError::new("empty polymer"))?;
     let mut pair_to_count = HashMap::new();
     for pair in line.chars().tuple_windows() {
         *(pair_to_count.entry(pair).or_insert(0)) += 1;
     }
-    State {
+    Ok(State {
         first_char,
         pair_to_count,
-    }
+    })
 }
 
 #[test]
@@ -55,7 +59,8 @@ fn test_parse_state() {
         first_char: 'A',
         pair_to_count,
     };
-    assert_eq!(expected, parse_state("ABBB"));
+    assert_eq!(expected, parse_state("ABBB").unwrap());
+    assert!(parse_state("").is_err());
 }
 
 /// Holds the contents of the input file.
@@ -75,35 +80,59 @@ lazy_static! {
 }
 
 /// Parses one rule
-fn parse_rule(line: &str) -> (Pair, char) {
+fn parse_rule(line: &str) -> Result<(Pair, char), AdventError> {
     match RULE_PATTERN.captures(line) {
-        None => {
-            panic!("bad rule: {:?}", line);
-        }
+        None => Err(AdventError::new("bad rule; expected one like CH -> B")),
         Some(captures) => {
             let lhs = (
                 captures[1].chars().next().unwrap(),
                 captures[2].chars().next().unwrap(),
             );
             let rhs = captures[3].chars().next().unwrap();
-            (lhs, rhs)
+            Ok((lhs, rhs))
         }
     }
 }
 
 /// Parses the entire input file
-fn parse_input(lines: &[&str]) -> Input {
-    let mut iter = lines.iter();
-    let initial_string = iter.next().unwrap().clone();
-    let initial_state = parse_state(&initial_string);
-    if *iter.next().unwrap() != "" {
-        panic!("expected blank second line");
+fn parse_input(lines: &[&str]) -> AdventResult<Input> {
+    let initial_state = match lines.first() {
+        Some(line) => parse_state(line).map_err(|e| at_line(Box::new(e), 1, line))?,
+        None => return Err(Box::new(AdventError::new("empty input"))),
+    };
+    match lines.get(1) {
+        Some(&"") => {}
+        Some(line) => {
+            return Err(Box::new(AdventError::parse(
+                2,
+                line,
+                "expected a blank line",
+            )))
+        }
+        None => {
+            return Err(Box::new(AdventError::new(
+                "expected a blank line, then rules",
+            )))
+        }
     }
-    let rules = iter.map(|line| parse_rule(line)).collect();
-    Input {
+    let rules = parse_lines(&lines[2..], 3, |line| Ok(parse_rule(line)?))?;
+    Ok(Input {
         initial_state,
-        rules,
-    }
+        rules: rules.into_iter().collect(),
+    })
+}
+
+#[test]
+fn test_parse_input() {
+    let input = parse_input(&["NNCB", "", "CH -> B", "HH -> N"]).unwrap();
+    assert_eq!(Some(&'B'), input.rules.get(&('C', 'H')));
+    assert!(parse_input(&["NNCB", "CH -> B"]).is_err());
+    assert_eq!(
+        "AdventError: parse error: line 4: \"HH => N\": bad rule; expected one like CH -> B",
+        parse_input(&["NNCB", "", "CH -> B", "HH => N"])
+            .unwrap_err()
+            .to_string()
+    );
 }
 
 /// Adds a delta to a counter stored in a hash map.
@@ -137,18 +166,21 @@ fn one_step(before: &State, rules: &HashMap<(char, char), char>) -> State {
 fn test_one_step() {
     let mut rules = HashMap::new();
     rules.insert(('B', 'D'), 'C');
-    assert_eq!(parse_state("ABCD"), one_step(&parse_state("ABD"), &rules));
+    assert_eq!(
+        parse_state("ABCD").unwrap(),
+        one_step(&parse_state("ABD").unwrap(), &rules)
+    );
 }
 
 fn day_14(lines: &[&str], step_count: usize) -> AdventResult<Answer> {
-    let input = parse_input(lines);
+    let input = parse_input(lines)?;
     let mut current = input.initial_state.clone();
     for _ in 0..step_count {
         current = one_step(&current, &input.rules);
     }
 
     // Count each char
-    let mut char_to_count: HashMap<char, Answer> = HashMap::new();
+    let mut char_to_count: HashMap<char, u64> = HashMap::new();
     add_to_count(current.first_char, 1, &mut char_to_count);
     for ((_, c), count) in current.pair_to_count {
         add_to_count(c, count, &mut char_to_count);
@@ -159,7 +191,7 @@ fn day_14(lines: &[&str], step_count: usize) -> AdventResult<Answer> {
     count_and_char.sort();
     let min_count = count_and_char[0].0;
     let max_count = count_and_char.last().unwrap().0;
-    Ok(max_count - min_count)
+    Ok((max_count - min_count).into())
 }
 
 fn day_14_a(lines: &[&str]) -> AdventResult<Answer> {
@@ -170,10 +202,43 @@ fn day_14_b(lines: &[&str]) -> AdventResult<Answer> {
     day_14(lines, 40)
 }
 
+/// Builds the whole polymer, instead of counting its pairs
+fn reference_a(lines: &[&str]) -> AdventResult<Answer> {
+    let input = parse_input(lines)?;
+    let mut polymer: Vec<char> = lines[0].chars().collect();
+    for _ in 0..10 {
+        let mut next = vec![polymer[0]];
+        for (&a, &b) in polymer.iter().tuple_windows() {
+            if let Some(&inserted) = input.rules.get(&(a, b)) {
+                next.push(inserted);
+            }
+            next.push(b);
+        }
+        polymer = next;
+    }
+    let char_to_count = polymer.iter().counts();
+    let max_count = char_to_count.values().max().unwrap();
+    let min_count = char_to_count.values().min().unwrap();
+    Ok((max_count - min_count).into())
+}
+
+/// A polymer made from some letters, and a rule for every pair of them
+fn generate(random: &mut Random, size: usize) -> Vec<String> {
+    let mut letters: Vec<char> = ('A'..='Z').collect();
+    random.shuffle(&mut letters);
+    let letters = &letters[..10];
+    let mut result = vec![(0..size).map(|_| *random.choose(letters)).collect()];
+    result.push("".to_string());
+    for a in letters {
+        for b in letters {
+            result.push(format!("{}{} -> {}", a, b, random.choose(letters)));
+        }
+    }
+    result
+}
+
 pub fn make_day_14() -> Day {
-    Day::new(
-        14,
-        DayPart::new(day_14_a, 1588, 2112),
-        DayPart::new(day_14_b, 2188189693529, 3243771149914),
-    )
+    Day::new(2021, 14, DayPart::new(day_14_a), DayPart::new(day_14_b))
+        .with_generator(generate, 20)
+        .with_reference(Some(reference_a), None, 5)
 }
@@ -0,0 +1,91 @@
+use crate::generate::Random;
+use crate::grid::{check_grid, parse_grid, GridView};
+use crate::pathfinding::grid_dijkstra;
+use crate::types::{AdventError, AdventResult, Answer, Day, DayPart};
+
+/// The lowest total risk of getting from the top left to the bottom right,
+/// where the grid is the cost to enter each cell
+fn lowest_cost(cost_to_enter: &impl GridView<Cell = u8>) -> AdventResult<Answer> {
+    let (width, height) = cost_to_enter.shape();
+    let bottom_right = (width - 1, height - 1);
+    let path = grid_dijkstra(cost_to_enter, (0, 0), bottom_right, |&cost| {
+        Some(cost as u64)
+    })
+    .ok_or_else(|| AdventError::new("no way to the bottom right"))?;
+    Ok(path.cost.into())
+}
+
+#[test]
+fn test_lowest_cost() {
+    let grid = parse_grid(&["1163", "1381", "2136"]).unwrap();
+    assert_eq!(Answer::from(13u64), lowest_cost(&grid).unwrap());
+
+    // Cells that are free to enter are fine
+    let grid = parse_grid(&["0090", "9090", "0000"]).unwrap();
+    assert_eq!(Answer::from(0u64), lowest_cost(&grid).unwrap());
+}
+
+fn day_15_a(lines: &[&str]) -> AdventResult<Answer> {
+    // The input grid is the cost to enter each cell
+    let cost_to_enter = parse_grid(lines)?;
+    lowest_cost(&cost_to_enter)
+}
+
+/// The risk of a cell in a tile that's `distance` tiles right and down
+/// from the original: one more for each tile, wrapping from 9 back to 1
+fn tile_risk(risk: u8, distance: usize) -> u8 {
+    if distance == 0 {
+        risk
+    } else {
+        ((risk as usize + distance - 1) % 9 + 1) as u8
+    }
+}
+
+fn day_15_b(lines: &[&str]) -> AdventResult<Answer> {
+    // The whole cave is the input grid repeated 5 times in each direction
+    let original = parse_grid(lines)?;
+    let cave = original.tiled((5, 5), |risk, (tx, ty)| tile_risk(risk, tx + ty));
+    lowest_cost(&cave)
+}
+
+#[test]
+fn test_tile_risk() {
+    assert_eq!(8, tile_risk(8, 0));
+    assert_eq!(9, tile_risk(8, 1));
+    assert_eq!(1, tile_risk(8, 2));
+    assert_eq!(8, tile_risk(8, 9));
+    assert_eq!(1, tile_risk(0, 1));
+}
+
+/// The input must be a grid of digits
+fn validate(lines: &[&str]) -> Vec<AdventError> {
+    check_grid(lines, |c| c.is_ascii_digit(), "digits")
+}
+
+#[test]
+fn test_validate() {
+    assert!(validate(&["116", "138"]).is_empty());
+    assert!(validate(&["106", "000"]).is_empty());
+    let problems = validate(&["106", "13", "0x0"]);
+    let lines: Vec<usize> = problems
+        .iter()
+        .map(|e| e.location().unwrap().line)
+        .collect();
+    assert_eq!(vec![2, 3], lines);
+}
+
+fn generate(random: &mut Random, size: usize) -> Vec<String> {
+    (0..size)
+        .map(|_| {
+            (0..size)
+                .map(|_| (b'1' + random.below(9) as u8) as char)
+                .collect()
+        })
+        .collect()
+}
+
+pub fn make_day_15() -> Day {
+    Day::new(2021, 15, DayPart::new(day_15_a), DayPart::new(day_15_b))
+        .with_validator(validate)
+        .with_generator(generate, 100)
+}
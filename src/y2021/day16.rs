@@ -0,0 +1,506 @@
+use crate::generate::Random;
+use crate::types::{at_line, AdventError, AdventResult, Answer, Day, DayPart};
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Bit {
+    Zero,
+    One,
+}
+
+use Bit::{One, Zero};
+
+fn value_of_hex(c: u8) -> Result<u8, AdventError> {
+    if b'0' <= c && c <= b'9' {
+        Ok(c - b'0')
+    } else if b'A' <= c && c <= b'F' {
+        Ok(10 + (c - b'A'))
+    } else {
+        Err(AdventError::new(&format!("bad hex digit: {:?}", c as char)))
+    }
+}
+
+#[test]
+fn test_value_of_hex() {
+    assert_eq!(3, value_of_hex(b'3').unwrap());
+    assert_eq!(13, value_of_hex(b'D').unwrap());
+    assert!(value_of_hex(b'g').is_err());
+}
+
+#[derive(Debug)]
+struct Biterator {
+    // the string of hex digits to decode
+    hex_values: Vec<u8>,
+
+    // the next bit index to return
+    bit_index: usize,
+}
+
+impl Biterator {
+    fn new(hex_digits: &str) -> Result<Biterator, AdventError> {
+        Ok(Biterator {
+            hex_values: hex_digits
+                .as_bytes()
+                .iter()
+                .map(|&c| value_of_hex(c))
+                .collect::<Result<_, _>>()?,
+            bit_index: 0,
+        })
+    }
+
+    fn next_bit(&mut self) -> Result<Bit, AdventError> {
+        self.next().ok_or_else(|| {
+            AdventError::new(&format!("packet ends early, after {} bits", self.bit_index))
+        })
+    }
+
+    fn next_number(&mut self, bit_count: usize) -> Result<usize, AdventError> {
+        let mut result = 0;
+        for _ in 0..bit_count {
+            result <<= 1;
+            if self.next_bit()? == One {
+                result += 1;
+            }
+        }
+        Ok(result)
+    }
+}
+
+#[test]
+fn test_next_number() {
+    let mut biterator = Biterator::new("57").unwrap();
+    assert_eq!(2, biterator.next_number(3).unwrap());
+    assert_eq!(23, biterator.next_number(5).unwrap());
+    assert!(biterator.next_number(1).is_err());
+}
+
+impl Iterator for Biterator {
+    type Item = Bit;
+
+    fn next(&mut self) -> Option<Bit> {
+        let byte_index = self.bit_index / 4;
+        if self.hex_values.len() <= byte_index {
+            None
+        } else {
+            let hex_value = self.hex_values[byte_index];
+            let mask = 1 << (3 - self.bit_index % 4);
+            if hex_value & mask == 0 {
+                self.bit_index += 1;
+                Some(Zero)
+            } else {
+                self.bit_index += 1;
+                Some(One)
+            }
+        }
+    }
+}
+
+#[test]
+fn test_biterator() {
+    assert_eq!(
+        vec![Zero, One, Zero, One, One, Zero, Zero, One],
+        Biterator::new("59").unwrap().collect::<Vec<Bit>>()
+    )
+}
+
+/// What a packet holds: a number, or more packets
+#[derive(Debug, PartialEq)]
+pub enum Contents {
+    Literal(usize),
+    Operator(Vec<Packet>),
+}
+
+use Contents::{Literal, Operator};
+
+/// One decoded BITS packet
+#[derive(Debug, PartialEq)]
+pub struct Packet {
+    pub version: usize,
+    pub type_id: usize,
+    pub contents: Contents,
+}
+
+fn parse_literal(biterator: &mut Biterator) -> Result<Contents, AdventError> {
+    let mut literal_value: usize = 0;
+    loop {
+        let last_group_bit = biterator.next_bit()?;
+        literal_value = literal_value
+            .checked_mul(16)
+            .ok_or_else(|| AdventError::new("literal is too big"))?
+            + biterator.next_number(4)?;
+        if last_group_bit == Zero {
+            return Ok(Literal(literal_value));
+        }
+    }
+}
+
+fn parse_operator(biterator: &mut Biterator) -> Result<Contents, AdventError> {
+    let mut sub_packets = Vec::new();
+    match biterator.next_bit()? {
+        Zero => {
+            let bit_length = biterator.next_number(15)?;
+            let target = biterator.bit_index + bit_length;
+            while biterator.bit_index < target {
+                sub_packets.push(parse_packet(biterator)?);
+            }
+            if biterator.bit_index != target {
+                return Err(AdventError::new("length mismatch in sub packets"));
+            }
+        }
+        One => {
+            let subpacket_count = biterator.next_number(11)?;
+            for _ in 0..subpacket_count {
+                sub_packets.push(parse_packet(biterator)?);
+            }
+        }
+    }
+    Ok(Operator(sub_packets))
+}
+
+fn parse_packet(biterator: &mut Biterator) -> Result<Packet, AdventError> {
+    let start = biterator.bit_index;
+    let version = biterator.next_number(3)?;
+    let type_id = biterator.next_number(3)?;
+    let contents = match type_id {
+        4 => parse_literal(biterator)?,
+        _ => parse_operator(biterator)?,
+    };
+
+    // Check that the operator can be evaluated
+    if let Operator(sub_packets) = &contents {
+        let count_ok = match type_id {
+            5..=7 => sub_packets.len() == 2,
+            _ => !sub_packets.is_empty(),
+        };
+        if !count_ok {
+            return Err(AdventError::new(&format!(
+                "operator {} at bit {} has {} sub packets",
+                type_id,
+                start,
+                sub_packets.len()
+            )));
+        }
+    }
+    Ok(Packet {
+        version,
+        type_id,
+        contents,
+    })
+}
+
+/// Decodes the packet in a string of hex digits
+pub fn parse_string(s: &str) -> Result<Packet, AdventError> {
+    let mut biterator = Biterator::new(s)?;
+    let result = parse_packet(&mut biterator)?;
+    for bit in biterator {
+        match bit {
+            Zero => {}
+            One => return Err(AdventError::new("One bit following end of packet")),
+        }
+    }
+    Ok(result)
+}
+
+/// Decodes the packet on the one line of input
+fn parse_input(lines: &[&str]) -> AdventResult<Packet> {
+    if lines.len() != 1 {
+        return Err(Box::new(AdventError::new(
+            "expected exactly one input line",
+        )));
+    }
+    parse_string(lines[0]).map_err(|e| at_line(Box::new(e), 1, lines[0]))
+}
+
+#[test]
+fn test_parse_packet() {
+    assert_eq!(
+        Packet {
+            version: 6,
+            type_id: 4,
+            contents: Literal(2021)
+        },
+        parse_string("D2FE28").unwrap()
+    );
+    assert_eq!(
+        Packet {
+            version: 1,
+            type_id: 6,
+            contents: Operator(vec![
+                Packet {
+                    version: 6,
+                    type_id: 4,
+                    contents: Literal(10)
+                },
+                Packet {
+                    version: 2,
+                    type_id: 4,
+                    contents: Literal(20)
+                }
+            ])
+        },
+        parse_string("38006F45291200").unwrap()
+    );
+    assert_eq!(
+        Packet {
+            version: 7,
+            type_id: 3,
+            contents: Operator(vec![
+                Packet {
+                    version: 2,
+                    type_id: 4,
+                    contents: Literal(1)
+                },
+                Packet {
+                    version: 4,
+                    type_id: 4,
+                    contents: Literal(2)
+                },
+                Packet {
+                    version: 1,
+                    type_id: 4,
+                    contents: Literal(3)
+                }
+            ])
+        },
+        parse_string("EE00D40C823060").unwrap()
+    );
+    assert!(parse_string("D2FE2").is_err());
+    assert!(parse_string("D2FE28X").is_err());
+    assert!(parse_string("D2FE2F").is_err());
+    // An operator with no sub packets
+    assert!(parse_string("00000000").is_err());
+}
+
+/// Adds up the version numbers of a packet and everything in it
+pub fn sum_versions(packet: &Packet) -> usize {
+    let mut result = packet.version;
+    if let Operator(sub_packets) = &packet.contents {
+        for sub_packet in sub_packets {
+            result += sum_versions(&sub_packet);
+        }
+    }
+    result
+}
+
+#[test]
+fn test_sum_versions() {
+    assert_eq!(
+        16,
+        sum_versions(&parse_string("8A004A801A8002F478").unwrap())
+    );
+    assert_eq!(
+        12,
+        sum_versions(&parse_string("620080001611562C8802118E34").unwrap())
+    );
+    assert_eq!(
+        23,
+        sum_versions(&parse_string("C0015000016115A2E0802F182340").unwrap())
+    );
+    assert_eq!(
+        31,
+        sum_versions(&parse_string("A0016C880162017C3686B18A3D4780").unwrap())
+    );
+}
+
+fn day_16_a(lines: &[&str]) -> AdventResult<Answer> {
+    Ok(sum_versions(&parse_input(lines)?).into())
+}
+
+/// Computes the value of the expression a packet represents
+pub fn evaluate(packet: &Packet) -> usize {
+    match &packet.contents {
+        Literal(n) => *n,
+        Operator(sub_packets) => {
+            let mut sub_values = sub_packets.iter().map(|p| evaluate(p));
+            match packet.type_id {
+                0 => sub_values.sum(),
+                1 => sub_values.product(),
+                // parse_packet checks how many sub packets there are
+                2 => sub_values.min().unwrap(),
+                3 => sub_values.max().unwrap(),
+                5 => {
+                    let a = sub_values.next().unwrap();
+                    let b = sub_values.next().unwrap();
+                    if a > b {
+                        1
+                    } else {
+                        0
+                    }
+                }
+                6 => {
+                    let a = sub_values.next().unwrap();
+                    let b = sub_values.next().unwrap();
+                    if a < b {
+                        1
+                    } else {
+                        0
+                    }
+                }
+                7 => {
+                    let a = sub_values.next().unwrap();
+                    let b = sub_values.next().unwrap();
+                    if a == b {
+                        1
+                    } else {
+                        0
+                    }
+                }
+
+                _ => panic!("unexpected type_id: {:?}", packet.type_id),
+            }
+        }
+    }
+}
+
+#[test]
+fn test_evaluate() {
+    assert_eq!(3, evaluate(&parse_string("C200B40A82").unwrap()));
+    assert_eq!(54, evaluate(&parse_string("04005AC33890").unwrap()));
+    assert_eq!(7, evaluate(&parse_string("880086C3E88112").unwrap()));
+    assert_eq!(9, evaluate(&parse_string("CE00C43D881120").unwrap()));
+    assert_eq!(1, evaluate(&parse_string("D8005AC2A8F0").unwrap()));
+    assert_eq!(0, evaluate(&parse_string("F600BC2D8F").unwrap()));
+    assert_eq!(0, evaluate(&parse_string("9C005AC2F8F0").unwrap()));
+    assert_eq!(
+        1,
+        evaluate(&parse_string("9C0141080250320F1802104A08").unwrap())
+    );
+}
+
+fn day_16_b(lines: &[&str]) -> AdventResult<Answer> {
+    Ok(evaluate(&parse_input(lines)?).into())
+}
+
+/// Adds the bits of a number, most significant first
+fn push_number(bits: &mut Vec<bool>, n: usize, bit_count: usize) {
+    for i in (0..bit_count).rev() {
+        bits.push(n & (1 << i) != 0);
+    }
+}
+
+/// Encodes a packet as bits.  Operators say how long their sub packets are
+/// in whichever way `use_count` picks, if it fits.
+fn encode(packet: &Packet, use_count: &mut impl FnMut() -> bool, bits: &mut Vec<bool>) {
+    push_number(bits, packet.version, 3);
+    push_number(bits, packet.type_id, 3);
+    match &packet.contents {
+        Literal(n) => {
+            let group_count = (1..).find(|i| *i == 16 || n >> (4 * i) == 0).unwrap();
+            for i in (0..group_count).rev() {
+                bits.push(i != 0);
+                push_number(bits, (n >> (4 * i)) & 0xF, 4);
+            }
+        }
+        Operator(sub_packets) => {
+            let mut sub_bits = Vec::new();
+            for sub_packet in sub_packets {
+                encode(sub_packet, use_count, &mut sub_bits);
+            }
+            if sub_bits.len() < (1 << 15) && !use_count() {
+                bits.push(false);
+                push_number(bits, sub_bits.len(), 15);
+            } else {
+                bits.push(true);
+                push_number(bits, sub_packets.len(), 11);
+            }
+            bits.extend(sub_bits);
+        }
+    }
+}
+
+/// Turns bits into hex digits, padding with zeros at the end
+fn to_hex(bits: &[bool]) -> String {
+    bits.chunks(4)
+        .map(|chunk| {
+            let digit = (0..4).fold(0, |n, i| n * 2 + *chunk.get(i).unwrap_or(&false) as u32);
+            std::char::from_digit(digit, 16)
+                .unwrap()
+                .to_ascii_uppercase()
+        })
+        .collect()
+}
+
+#[test]
+fn test_encode() {
+    for hex in [
+        "D2FE28",
+        "38006F45291200",
+        "EE00D40C823060",
+        "8A004A801A8002F478",
+    ] {
+        let packet = parse_string(hex).unwrap();
+        let mut bits = Vec::new();
+        encode(&packet, &mut || hex.starts_with("EE"), &mut bits);
+        assert_eq!(packet, parse_string(&to_hex(&bits)).unwrap());
+    }
+}
+
+/// Makes a random packet with about `budget` packets in it, and returns its
+/// value too.  Products that would get too big become sums instead.
+fn random_packet(random: &mut Random, budget: &mut usize, depth: usize) -> (Packet, usize) {
+    let version = random.below(8);
+    *budget = budget.saturating_sub(1);
+    if *budget == 0 || 10 < depth || random.chance(0.3) {
+        let value = if random.chance(0.1) {
+            random.below(1 << 32)
+        } else {
+            random.below(4096)
+        };
+        let packet = Packet {
+            version,
+            type_id: 4,
+            contents: Literal(value),
+        };
+        return (packet, value);
+    }
+    let mut type_id = *random.choose(&[0, 1, 2, 3, 5, 6, 7]);
+    let count = match type_id {
+        5..=7 => 2,
+        _ => random.range(1..=4) as usize,
+    };
+    let (sub_packets, values): (Vec<Packet>, Vec<usize>) = (0..count)
+        .map(|_| random_packet(random, budget, depth + 1))
+        .unzip();
+    let value = match type_id {
+        0 => values.iter().sum(),
+        1 => match values.iter().try_fold(1usize, |p, &v| p.checked_mul(v)) {
+            Some(product) if product < (1 << 40) => product,
+            _ => {
+                type_id = 0;
+                values.iter().sum()
+            }
+        },
+        2 => *values.iter().min().unwrap(),
+        3 => *values.iter().max().unwrap(),
+        5 => (values[0] > values[1]) as usize,
+        6 => (values[0] < values[1]) as usize,
+        _ => (values[0] == values[1]) as usize,
+    };
+    let packet = Packet {
+        version,
+        type_id,
+        contents: Operator(sub_packets),
+    };
+    (packet, value)
+}
+
+/// A transmission with about `size` packets in it
+fn generate(random: &mut Random, size: usize) -> Vec<String> {
+    let mut budget = size;
+    let (mut packet, _) = random_packet(random, &mut budget, 0);
+    // Keep adding to it, until there are enough packets
+    while 0 < budget {
+        let (more, _) = random_packet(random, &mut budget, 1);
+        packet = Packet {
+            version: random.below(8),
+            type_id: 0,
+            contents: Operator(vec![packet, more]),
+        };
+    }
+    let mut bits = Vec::new();
+    encode(&packet, &mut || random.chance(0.5), &mut bits);
+    vec![to_hex(&bits)]
+}
+
+pub fn make_day_16() -> Day {
+    Day::new(2021, 16, DayPart::new(day_16_a), DayPart::new(day_16_b)).with_generator(generate, 200)
+}
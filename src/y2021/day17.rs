@@ -0,0 +1,185 @@
+use std::cmp::max;
+use std::ops::RangeInclusive;
+
+use regex::Regex;
+
+use crate::generate::Random;
+use crate::types::{at_line, AdventError, AdventResult, Answer, Day, DayPart};
+
+/// Holds the x and y ranges that are the target area
+#[derive(Debug, PartialEq)]
+struct Target {
+    x_range: RangeInclusive<i32>,
+    y_range: RangeInclusive<i32>,
+}
+
+fn parse_target(line: &str) -> AdventResult<Target> {
+    let pattern =
+        Regex::new(r"^target area: x=(-?[0-9]+)[.][.](-?[0-9]+), y=(-?[0-9]+)[.][.](-?[0-9]+)$")
+            .unwrap();
+    let captures = pattern
+        .captures(line)
+        .ok_or_else(|| AdventError::new("expected a target area like x=20..30, y=-10..-5"))?;
+    let x_min = captures[1].parse()?;
+    let x_max = captures[2].parse()?;
+    let y_min = captures[3].parse()?;
+    let y_max = captures[4].parse()?;
+    if x_max < x_min || y_max < y_min {
+        return Err(Box::new(AdventError::new(
+            "ranges must go from low to high",
+        )));
+    }
+    Ok(Target {
+        x_range: x_min..=x_max,
+        y_range: y_min..=y_max,
+    })
+}
+
+#[test]
+fn test_parse_target() {
+    assert_eq!(
+        Target {
+            x_range: 20..=30,
+            y_range: -10..=-5
+        },
+        parse_target("target area: x=20..30, y=-10..-5").unwrap()
+    );
+    assert!(parse_target("target area: x=20..30").is_err());
+    assert!(parse_target("target area: x=20..30, y=-10..-99999999999").is_err());
+    assert!(parse_target("target area: x=20..10, y=-10..-5").is_err());
+    assert!(parse_target("target area: x=20..30, y=-5..-10").is_err());
+
+    let error = parse_input(&["target area: x=20..10, y=-10..-5"]).unwrap_err();
+    assert_eq!(
+        "AdventError: parse error: line 1: \"target area: x=20..10, y=-10..-5\": \
+         ranges must go from low to high",
+        error.to_string()
+    );
+}
+
+fn parse_input(lines: &[&str]) -> AdventResult<Target> {
+    if lines.len() != 1 {
+        return Err(Box::new(AdventError::new(
+            "expected exactly one input line",
+        )));
+    }
+    parse_target(lines[0]).map_err(|e| at_line(e, 1, lines[0]))
+}
+
+/// Does the given initial velocity hit the target?
+fn hits_target(initial_vx: i32, initial_vy: i32, target: &Target) -> bool {
+    let mut x = 0;
+    let mut y = 0;
+    let mut vx = initial_vx;
+    let mut vy = initial_vy;
+    while x <= *target.x_range.end() && *target.y_range.start() <= y {
+        if target.x_range.contains(&x) && target.y_range.contains(&y) {
+            return true;
+        }
+        x += vx;
+        y += vy;
+        vx = max(0, vx - 1);
+        vy = vy - 1;
+    }
+    false
+}
+
+/// Returns all of the initial velocities that hit the target.
+fn all_velocities(target: &Target) -> Vec<(i32, i32)> {
+    let mut result = Vec::new();
+    // This assumes that the target is off to the right, and so
+    // the initial x velocity must be positive.
+    //
+    // The initial velocity can't be bigger than the far side of
+    // the target; if it were, the first position would be past the
+    // target.
+    for vx in 1..=*target.x_range.end() {
+        // This assumes that the target is below the starting position.
+        //
+        // The initial y velocity can't be more negative than the
+        // bottom of the target; if it were, the first position would
+        // be past the target.
+        //
+        // The initial y velocity can't be more positive than the distance
+        // to the bottom of the target; if it were, it would go up, come
+        // back to a y of 0 with the same velocity, but negative, and then
+        // go past the target on the next step.
+        for vy in *target.y_range.start()..=-*target.y_range.start() {
+            if hits_target(vx, vy, target) {
+                result.push((vx, vy));
+            }
+        }
+    }
+    result
+}
+
+fn day_17_a(lines: &[&str]) -> AdventResult<Answer> {
+    let target = parse_input(lines)?;
+    let all = all_velocities(&target);
+    let max_vy = *all
+        .iter()
+        .map(|(_, vy)| vy)
+        .max()
+        .ok_or_else(|| AdventError::no_solution("no velocity hits the target"))?;
+    if max_vy < 0 {
+        return Err(Box::new(AdventError::no_solution(
+            "the probe can't go up and still hit the target",
+        )));
+    }
+    let max_y = (max_vy + max_vy * max_vy) / 2;
+    Ok((max_y as u64).into())
+}
+
+#[test]
+fn test_day_17_a() {
+    use crate::types::ErrorKind;
+
+    let kind = |line| {
+        day_17_a(&[line])
+            .unwrap_err()
+            .downcast::<AdventError>()
+            .unwrap()
+            .kind()
+    };
+    assert_eq!(
+        Answer::from(45u64),
+        day_17_a(&["target area: x=20..30, y=-10..-5"]).unwrap()
+    );
+    // Too close to the start to hit with an x velocity of at least 1
+    assert_eq!(
+        ErrorKind::NoSolution,
+        kind("target area: x=0..0, y=-10..-5")
+    );
+    // Only reached on the first step, so the probe has to go down
+    assert_eq!(
+        ErrorKind::NoSolution,
+        kind("target area: x=11..11, y=-10..-10")
+    );
+}
+
+fn day_17_b(lines: &[&str]) -> AdventResult<Answer> {
+    let target = parse_input(lines)?;
+    let all = all_velocities(&target);
+    Ok(all.len().into())
+}
+
+/// A target off to the right and below the start.  The size is about how far
+/// away it is.
+fn generate(random: &mut Random, size: usize) -> Vec<String> {
+    let size = size as i64;
+    let x_start = random.range(size / 2..=size);
+    let x_end = x_start + random.range(size / 10..=size / 2);
+    let y_start = -random.range(size / 2..=size);
+    let y_end = y_start + random.range(size / 10..=size / 3);
+    vec![format!(
+        "target area: x={}..{}, y={}..{}",
+        x_start,
+        x_end,
+        y_start,
+        y_end.min(-1)
+    )]
+}
+
+pub fn make_day_17() -> Day {
+    Day::new(2021, 17, DayPart::new(day_17_a), DayPart::new(day_17_b)).with_generator(generate, 150)
+}
@@ -8,7 +8,8 @@ use itertools::iproduct;
 #[cfg(test)]
 use itertools::Itertools;
 
-use crate::types::{AdventError, AdventResult, Answer, Day, DayPart};
+use crate::generate::Random;
+use crate::types::{parse_lines, AdventError, AdventResult, Answer, Day, DayPart};
 
 /// At the top level, every Snailfish Number is a pair.
 ///
@@ -50,18 +51,21 @@ impl SnailfishNumber {
     /// because all of the numbers are single digits.  For tests, though,
     /// we want to be able to parse non-reduced numbers, so we need to
     /// be able to peek ahead and see if there's more of the number.
-    fn parse<I>(iter: &mut iter::Peekable<I>) -> SnailfishNumber
+    fn parse<I>(iter: &mut iter::Peekable<I>) -> Result<SnailfishNumber, AdventError>
     where
         I: Iterator<Item = char>,
     {
-        let c: char = iter.next().unwrap();
-        if c.is_digit(10) {
-            let mut n = c.to_digit(10).unwrap() as u8;
+        let c: char = next_char(iter)?;
+        if let Some(digit) = c.to_digit(10) {
+            let mut n = digit as u8;
             loop {
                 if let Some(c) = iter.peek() {
                     if let Some(next_n) = c.to_digit(10) {
                         iter.next();
-                        n = n * 10 + (next_n as u8);
+                        n = n
+                            .checked_mul(10)
+                            .and_then(|n| n.checked_add(next_n as u8))
+                            .ok_or_else(|| AdventError::new("number too big"))?;
                     } else {
                         break;
                     }
@@ -70,28 +74,40 @@ impl SnailfishNumber {
                 }
             }
 
-            SnailfishNumber::regular(n)
+            Ok(SnailfishNumber::regular(n))
         } else if c == '[' {
-            let left = SnailfishNumber::parse(iter);
-            if iter.next().unwrap() != ',' {
-                panic!("expected comma");
+            let left = SnailfishNumber::parse(iter)?;
+            if next_char(iter)? != ',' {
+                return Err(AdventError::new("expected comma"));
             }
-            let right = SnailfishNumber::parse(iter);
-            if iter.next().unwrap() != ']' {
-                panic!("expected comma");
+            let right = SnailfishNumber::parse(iter)?;
+            if next_char(iter)? != ']' {
+                return Err(AdventError::new("expected close bracket"));
             }
-            SnailfishNumber::pair(&left, &right)
+            Ok(SnailfishNumber::pair(&left, &right))
         } else {
-            panic!("bad number: {:?}", c);
+            Err(AdventError::new(&format!("bad number: {:?}", c)))
         }
     }
 }
 
+/// The next character, which must be there
+fn next_char<I>(iter: &mut I) -> Result<char, AdventError>
+where
+    I: Iterator<Item = char>,
+{
+    iter.next()
+        .ok_or_else(|| AdventError::new("number ends early"))
+}
+
 impl FromStr for SnailfishNumber {
     type Err = AdventError;
     fn from_str(s: &str) -> Result<SnailfishNumber, AdventError> {
         let mut iter = s.chars().peekable();
-        let result = SnailfishNumber::parse(&mut iter);
+        let result = SnailfishNumber::parse(&mut iter)?;
+        if iter.next().is_some() {
+            return Err(AdventError::new("extra characters after number"));
+        }
         Ok(result)
     }
 }
@@ -121,6 +137,19 @@ fn test_from_str() {
         ),
         SnailfishNumber::from_str("[1,[2,10]]").unwrap()
     );
+    assert!(SnailfishNumber::from_str("[1,2").is_err());
+    assert!(SnailfishNumber::from_str("[1;2]").is_err());
+    assert!(SnailfishNumber::from_str("[1,2]]").is_err());
+    assert!(SnailfishNumber::from_str("[1,x]").is_err());
+    assert!(SnailfishNumber::from_str("").is_err());
+}
+
+fn parse_input(lines: &[&str]) -> AdventResult<Vec<SnailfishNumber>> {
+    let numbers = parse_lines(lines, 1, |line| Ok(SnailfishNumber::from_str(line)?))?;
+    if numbers.is_empty() {
+        return Err(Box::new(AdventError::new("no numbers to add")));
+    }
+    Ok(numbers)
 }
 
 impl fmt::Debug for SnailfishNumber {
@@ -299,9 +328,9 @@ fn test_reduce() {
 }
 
 /// Computes the magnitude of a SnailfishNumber
-fn magnitude(number: &SnailfishNumber) -> Answer {
+fn magnitude(number: &SnailfishNumber) -> u64 {
     match number.details() {
-        Regular(n) => *n as Answer,
+        Regular(n) => *n as u64,
         Pair(left, right) => 3 * magnitude(left) + 2 * magnitude(right),
     }
 }
@@ -343,32 +372,42 @@ fn test_add() {
 }
 
 fn day_18_a(lines: &[&str]) -> AdventResult<Answer> {
-    let sum = lines
-        .iter()
-        .map(|line| SnailfishNumber::from_str(line).unwrap())
+    let sum = parse_input(lines)?
+        .into_iter()
         .reduce(|a, b| add(&a, &b))
         .unwrap();
 
-    Ok(magnitude(&sum))
+    Ok(magnitude(&sum).into())
 }
 
 fn day_18_b(lines: &[&str]) -> AdventResult<Answer> {
-    let numbers: Vec<_> = lines
-        .iter()
-        .map(|line| SnailfishNumber::from_str(line).unwrap())
-        .collect();
+    let numbers = parse_input(lines)?;
     let max_magnitude = iproduct!(&numbers, &numbers)
         .filter(|(a, b)| a != b)
         .map(|(a, b)| max(magnitude(&add(a, b)), magnitude(&add(b, a))))
         .max()
         .unwrap();
-    Ok(max_magnitude)
+    Ok(max_magnitude.into())
+}
+
+/// A snailfish number nested no more than four deep, like the ones in the
+/// homework
+fn random_number(random: &mut Random, depth: usize) -> String {
+    if depth == 4 || (0 < depth && random.chance(0.3)) {
+        random.below(10).to_string()
+    } else {
+        format!(
+            "[{},{}]",
+            random_number(random, depth + 1),
+            random_number(random, depth + 1)
+        )
+    }
+}
+
+fn generate(random: &mut Random, size: usize) -> Vec<String> {
+    (0..size).map(|_| random_number(random, 0)).collect()
 }
 
 pub fn make_day_18() -> Day {
-    Day::new(
-        18,
-        DayPart::new(day_18_a, 4140, 3494),
-        DayPart::new(day_18_b, 3993, 4712),
-    )
+    Day::new(2021, 18, DayPart::new(day_18_a), DayPart::new(day_18_b)).with_generator(generate, 100)
 }
@@ -4,7 +4,8 @@ use std::ops;
 
 use itertools::iproduct;
 
-use crate::types::{AdventResult, Answer, Day, DayPart};
+use crate::generate::Random;
+use crate::types::{parse_lines, AdventError, AdventResult, Answer, Context, Day, DayPart};
 
 /// A point in 3-D space, with integer coordinates
 #[derive(Clone, Copy, Eq, Hash, PartialOrd, Ord, PartialEq)]
@@ -71,20 +72,25 @@ fn test_point_math() {
     );
 }
 
-fn parse_point(s: &str) -> Point {
-    let mut words = s.split(",");
-    let x: i32 = words.next().unwrap().parse().unwrap();
-    let y: i32 = words.next().unwrap().parse().unwrap();
-    let z: i32 = words.next().unwrap().parse().unwrap();
-    if !words.next().is_none() {
-        panic!("too many numbers in Point")
+fn parse_point(s: &str) -> AdventResult<Point> {
+    let words: Vec<&str> = s.split(",").collect();
+    if words.len() != 3 {
+        return Err(Box::new(AdventError::new(
+            "expected three numbers in Point",
+        )));
     }
-    Point { x, y, z }
+    let x: i32 = words[0].parse()?;
+    let y: i32 = words[1].parse()?;
+    let z: i32 = words[2].parse()?;
+    Ok(Point { x, y, z })
 }
 
 #[test]
 fn test_parse_point() {
-    assert_eq!(Point::new(1, -2, 3), parse_point("1,-2,3"));
+    assert_eq!(Point::new(1, -2, 3), parse_point("1,-2,3").unwrap());
+    assert!(parse_point("1,-2").is_err());
+    assert!(parse_point("1,-2,3,4").is_err());
+    assert!(parse_point("1,-2,x").is_err());
 }
 
 fn manhattan_distance(a: &Point, b: &Point) -> i32 {
@@ -327,17 +333,35 @@ fn test_all_rotations() {
     }
 }
 
-/// Parse the locatons of the beacons from one scanner
-fn parse_beacons(lines: &[&str]) -> HashSet<Point> {
-    lines.iter().skip(1).map(|&s| parse_point(s)).collect()
+/// Parse the locatons of the beacons from one scanner, which start at line
+/// `first_line` of the input
+fn parse_beacons(lines: &[&str], first_line: usize) -> AdventResult<HashSet<Point>> {
+    match lines.first() {
+        Some(header) if header.starts_with("---") => {}
+        Some(header) => {
+            return Err(Box::new(AdventError::parse(
+                first_line,
+                header,
+                "expected a scanner header, like --- scanner 0 ---",
+            )))
+        }
+        None => return Err(Box::new(AdventError::new("missing scanner"))),
+    }
+    let points = parse_lines(&lines[1..], first_line + 1, parse_point)?;
+    Ok(points.into_iter().collect())
 }
 
 /// Parse the input file, containing reports from all scanners
-fn parse_input(lines: &[&str]) -> Vec<HashSet<Point>> {
-    lines
-        .split(|line| *line == "")
-        .map(|sub_lines| parse_beacons(sub_lines))
-        .collect()
+fn parse_input(lines: &[&str]) -> AdventResult<Vec<HashSet<Point>>> {
+    let mut result = Vec::new();
+    let mut first_line = 1;
+    for sub_lines in lines.split(|line| *line == "") {
+        let beacons = parse_beacons(sub_lines, first_line)
+            .with_context(|| format!("scanner {}", result.len()))?;
+        result.push(beacons);
+        first_line += sub_lines.len() + 1;
+    }
+    Ok(result)
 }
 
 #[test]
@@ -357,7 +381,15 @@ fn test_parse_input() {
             "--- sensor 1 ---",
             "7,8,9",
         ])
+        .unwrap()
     );
+    assert_eq!(
+        "AdventError: parse error: scanner 1: line 5: \"7,8\": expected three numbers in Point",
+        parse_input(&["--- sensor 0 ---", "1,2,3", "", "--- sensor 1 ---", "7,8"])
+            .unwrap_err()
+            .to_string()
+    );
+    assert!(parse_input(&["1,2,3"]).is_err());
 }
 
 /// Returns all rotations of a set of points, with each one being a sorted
@@ -462,9 +494,9 @@ fn find_match(a_points: &Vec<Point>, b_rotations: &Vec<Vec<Point>>) -> Option<(P
 #[test]
 fn test_find_match() {
     let lines_in_file =
-        crate::util::lines_in_file(std::path::Path::new("input/day-19/sample.txt")).unwrap();
+        crate::util::lines_in_file(std::path::Path::new("input/2021/day-19/sample.txt")).unwrap();
     let strs_in_file: Vec<&str> = lines_in_file.iter().map(|s| &s[..]).collect();
-    let sets = pre_process_input(&parse_input(&strs_in_file[..]));
+    let sets = pre_process_input(&parse_input(&strs_in_file[..]).unwrap());
 
     let (sensor_1_position, sensor_1_points) = find_match(&sets[0][0], &sets[1]).unwrap();
     assert_eq!(Point::new(68, -1246, -43), sensor_1_position);
@@ -508,8 +540,8 @@ fn match_with_done(
     None
 }
 
-fn find_all_matches(lines: &[&str]) -> Vec<(Point, Vec<Point>)> {
-    let sets = pre_process_input(&parse_input(lines));
+fn find_all_matches(lines: &[&str]) -> AdventResult<Vec<(Point, Vec<Point>)>> {
+    let sets = pre_process_input(&parse_input(lines)?);
 
     // The 'done' vector is parallel to sets, and tracks which ones
     // have been matched and located.  For each one that's done, we
@@ -539,7 +571,7 @@ fn find_all_matches(lines: &[&str]) -> Vec<(Point, Vec<Point>)> {
                 if let Some((d, offset_u, points_u)) =
                     match_with_done(&done, &to_check, rotations_u)
                 {
-                    println!("    Sensor {:?} is at {:?} matches {:?}", u, offset_u, d);
+                    eprintln!("    Sensor {:?} is at {:?} matches {:?}", u, offset_u, d);
                     done[u] = Some((offset_u, points_u.clone()));
                     done_count += 1;
                     new_to_check.insert(u);
@@ -547,20 +579,27 @@ fn find_all_matches(lines: &[&str]) -> Vec<(Point, Vec<Point>)> {
             }
         }
         if new_to_check.len() == 0 {
-            panic!("no progress");
+            let unmatched: Vec<String> = (0..sets.len())
+                .filter(|&u| done[u].is_none())
+                .map(|u| u.to_string())
+                .collect();
+            return Err(Box::new(AdventError::no_solution(&format!(
+                "scanners {} don't overlap with the others",
+                unmatched.join(", ")
+            ))));
         }
         to_check = new_to_check;
     }
 
-    done.into_iter().map(|item| item.unwrap()).collect()
+    Ok(done.into_iter().map(|item| item.unwrap()).collect())
 }
 
 #[test]
 fn test_find_all_matches() {
     let lines_in_file =
-        crate::util::lines_in_file(std::path::Path::new("input/day-19/sample.txt")).unwrap();
+        crate::util::lines_in_file(std::path::Path::new("input/2021/day-19/sample.txt")).unwrap();
     let strs_in_file: Vec<&str> = lines_in_file.iter().map(|s| &s[..]).collect();
-    let answers = find_all_matches(&strs_in_file);
+    let answers = find_all_matches(&strs_in_file).unwrap();
     assert_eq!(Point::new(0, 0, 0), answers[0].0);
     assert_eq!(Point::new(68, -1246, -43), answers[1].0);
     assert_eq!(Point::new(1105, -1205, 1229), answers[2].0);
@@ -569,17 +608,17 @@ fn test_find_all_matches() {
 }
 
 fn day_19_a(lines: &[&str]) -> AdventResult<Answer> {
-    let all_probes: HashSet<_> = find_all_matches(lines)
+    let all_probes: HashSet<_> = find_all_matches(lines)?
         .iter()
         .map(|(_, points)| points)
         .flatten()
         .map(|&p| p)
         .collect();
-    Ok(all_probes.len() as Answer)
+    Ok(all_probes.len().into())
 }
 
 fn day_19_b(lines: &[&str]) -> AdventResult<Answer> {
-    let all_locations: Vec<_> = find_all_matches(lines)
+    let all_locations: Vec<_> = find_all_matches(lines)?
         .iter()
         .map(|(location, _)| *location)
         .collect();
@@ -587,13 +626,73 @@ fn day_19_b(lines: &[&str]) -> AdventResult<Answer> {
         .map(|(a, b)| manhattan_distance(a, b))
         .max()
         .unwrap();
-    Ok(max_distance as Answer)
+    Ok((max_distance as u64).into())
+}
+
+/// Scanners that each overlap with at least one scanner before them, by 12
+/// beacons.  Each one sees its beacons turned a random way.
+fn generate(random: &mut Random, size: usize) -> Vec<String> {
+    let mut scanners = vec![Point::new(0, 0, 0)];
+    let mut beacons: HashSet<Point> = HashSet::new();
+    let random_point = |random: &mut Random, low: Point, high: Point| {
+        Point::new(
+            random.range(low.x as i64..=high.x as i64) as i32,
+            random.range(low.y as i64..=high.y as i64) as i32,
+            random.range(low.z as i64..=high.z as i64) as i32,
+        )
+    };
+    let reach = Point::new(1000, 1000, 1000);
+    while scanners.len() < size {
+        let neighbor = *random.choose(&scanners);
+        let offset = random_point(
+            random,
+            -Point::new(1200, 1200, 1200),
+            Point::new(1200, 1200, 1200),
+        );
+        let scanner = neighbor + offset;
+        // The beacons both scanners can see
+        let low = Point::new(
+            neighbor.x.max(scanner.x),
+            neighbor.y.max(scanner.y),
+            neighbor.z.max(scanner.z),
+        ) - reach;
+        let high = Point::new(
+            neighbor.x.min(scanner.x),
+            neighbor.y.min(scanner.y),
+            neighbor.z.min(scanner.z),
+        ) + reach;
+        for _ in 0..12 {
+            beacons.insert(random_point(random, low, high));
+        }
+        scanners.push(scanner);
+    }
+    for &scanner in scanners.iter() {
+        for _ in 0..10 {
+            beacons.insert(random_point(random, scanner - reach, scanner + reach));
+        }
+    }
+    let mut beacons: Vec<Point> = beacons.into_iter().collect();
+    beacons.sort();
+
+    let mut result = Vec::new();
+    for (i, &scanner) in scanners.iter().enumerate() {
+        if 0 < i {
+            result.push("".to_string());
+        }
+        result.push(format!("--- scanner {} ---", i));
+        let rotation = random.choose(&ALL_ROTATIONS);
+        let mut seen: Vec<Point> = beacons
+            .iter()
+            .map(|&b| b - scanner)
+            .filter(|p| p.x.abs() <= 1000 && p.y.abs() <= 1000 && p.z.abs() <= 1000)
+            .map(rotation)
+            .collect();
+        random.shuffle(&mut seen);
+        result.extend(seen.iter().map(|p| format!("{},{},{}", p.x, p.y, p.z)));
+    }
+    result
 }
 
 pub fn make_day_19() -> Day {
-    Day::new(
-        19,
-        DayPart::new(day_19_a, 79, 350),
-        DayPart::new(day_19_b, 3621, 10895),
-    )
+    Day::new(2021, 19, DayPart::new(day_19_a), DayPart::new(day_19_b)).with_generator(generate, 30)
 }
@@ -0,0 +1,137 @@
+use std::io::BufRead;
+use std::str::FromStr;
+
+use crate::generate::Random;
+use crate::types::{at_line, AdventError, AdventResult, Answer, Day, DayPart};
+
+#[derive(Debug, PartialEq)]
+enum SubmarineDirection {
+    Up,
+    Down,
+    Forward,
+}
+
+impl FromStr for SubmarineDirection {
+    type Err = AdventError;
+
+    fn from_str(s: &str) -> Result<SubmarineDirection, Self::Err> {
+        match s {
+            "up" => Ok(SubmarineDirection::Up),
+            "down" => Ok(SubmarineDirection::Down),
+            "forward" => Ok(SubmarineDirection::Forward),
+            _ => Err(AdventError::new(&format!(
+                "unknown submarine direction: {}",
+                s
+            ))),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct SubmarineCommand {
+    direction: SubmarineDirection,
+    distance: u64,
+}
+
+impl FromStr for SubmarineCommand {
+    type Err = AdventError;
+
+    fn from_str(s: &str) -> Result<SubmarineCommand, Self::Err> {
+        let (direction, distance) = s
+            .split_once(' ')
+            .ok_or_else(|| AdventError::new("expected a direction and a distance"))?;
+        let direction: SubmarineDirection = direction.parse()?;
+        let distance: u64 = distance
+            .parse()
+            .map_err(|_| AdventError::new(&format!("bad distance: {:?}", distance)))?;
+        Ok(SubmarineCommand {
+            direction,
+            distance,
+        })
+    }
+}
+
+#[test]
+fn test_submarine_command() {
+    assert_eq!(
+        SubmarineCommand {
+            direction: SubmarineDirection::Forward,
+            distance: 45
+        },
+        SubmarineCommand::from_str("forward 45").unwrap()
+    );
+    assert!(SubmarineCommand::from_str("forward").is_err());
+    assert!(SubmarineCommand::from_str("backward 3").is_err());
+    assert!(SubmarineCommand::from_str("up x").is_err());
+}
+
+/// Reads the commands in the input, one at a time
+fn commands(input: &mut dyn BufRead) -> impl Iterator<Item = AdventResult<SubmarineCommand>> + '_ {
+    input.lines().enumerate().map(|(i, line)| {
+        let line = line?;
+        line.parse()
+            .map_err(|e: AdventError| at_line(Box::new(e), i + 1, &line))
+    })
+}
+
+fn day_2_a(input: &mut dyn BufRead) -> AdventResult<Answer> {
+    let mut distance = 0;
+    let mut depth = 0;
+    for command in commands(input) {
+        let command = command?;
+        match command.direction {
+            SubmarineDirection::Up => depth -= command.distance,
+            SubmarineDirection::Down => depth += command.distance,
+            SubmarineDirection::Forward => distance += command.distance,
+        }
+    }
+    Ok((distance * depth).into())
+}
+
+fn day_2_b(input: &mut dyn BufRead) -> AdventResult<Answer> {
+    let mut distance = 0;
+    let mut depth = 0;
+    let mut aim = 0;
+    for command in commands(input) {
+        let command = command?;
+        match command.direction {
+            SubmarineDirection::Up => aim -= command.distance,
+            SubmarineDirection::Down => aim += command.distance,
+            SubmarineDirection::Forward => {
+                distance += command.distance;
+                depth += aim * command.distance;
+            }
+        }
+    }
+    Ok((distance * depth).into())
+}
+
+/// Commands that never go above the surface
+fn generate(random: &mut Random, size: usize) -> Vec<String> {
+    let mut aim = 0;
+    (0..size)
+        .map(|_| {
+            let distance = random.range(1..=9);
+            let direction = *random.choose(&["forward", "forward", "down", "up"]);
+            match direction {
+                "up" if distance <= aim => aim -= distance,
+                "up" | "down" => {
+                    aim += distance;
+                    return format!("down {}", distance);
+                }
+                _ => {}
+            }
+            format!("{} {}", direction, distance)
+        })
+        .collect()
+}
+
+pub fn make_day_2() -> Day {
+    Day::new(
+        2021,
+        2,
+        DayPart::streaming(day_2_a),
+        DayPart::streaming(day_2_b),
+    )
+    .with_generator(generate, 1000)
+}
@@ -0,0 +1,248 @@
+use crate::generate::Random;
+use crate::sparse_grid::{SignedPoint, SparseGrid};
+use crate::types::{AdventError, AdventResult, Answer, Day, DayPart};
+
+// Iterator over the neighbors of a point, including the point itself
+struct Neighbors {
+    p: SignedPoint,
+    i: usize,
+}
+
+impl Iterator for Neighbors {
+    type Item = SignedPoint;
+    fn next(&mut self) -> Option<SignedPoint> {
+        let index = self.i;
+        if index < 9 {
+            self.i += 1;
+            Some((
+                self.p.0 - 1 + ((index % 3) as i64),
+                self.p.1 - 1 + ((index / 3) as i64),
+            ))
+        } else {
+            None
+        }
+    }
+}
+
+fn neighbors(p: SignedPoint) -> Neighbors {
+    Neighbors { p, i: 0 }
+}
+
+#[test]
+fn test_neighbors() {
+    let neighbors: Vec<_> = neighbors((5, 5)).collect();
+    assert_eq!(
+        vec![4, 5, 6, 4, 5, 6, 4, 5, 6],
+        neighbors.iter().map(|p| p.0).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![4, 4, 4, 5, 5, 5, 6, 6, 6],
+        neighbors.iter().map(|p| p.1).collect::<Vec<_>>()
+    );
+}
+
+// An algorithm is an array of 0s/1s indicating whether an
+// output pixel is on based on the nine pixels in the block
+// around an input pixel.
+type Algorithm = [u8; 512];
+
+// An image that goes on forever.  Pixels are 0 for '.', and 1 for '#', and
+// the background is what all of the pixels far away from the middle are.
+type Image = SparseGrid<u8>;
+
+// Returns the number of pixels that are on, unless there are infinitely many
+fn pixel_on_count(image: &Image) -> AdventResult<usize> {
+    if *image.background() == 0 {
+        Ok(image.len())
+    } else {
+        Err(Box::new(AdventError::no_solution(
+            "infinitely many pixels are on",
+        )))
+    }
+}
+
+struct Input {
+    algorithm: Algorithm,
+    image: Image,
+}
+
+/// Reads a pixel: '.' is off, and '#' is on
+fn parse_pixel(c: char) -> Option<u8> {
+    match c {
+        '.' => Some(0),
+        '#' => Some(1),
+        _ => None,
+    }
+}
+
+/// Parses the algorithm, which can be split over several lines.  Bits that
+/// aren't given are 0.
+fn parse_algorithm(lines: &[&str]) -> AdventResult<Algorithm> {
+    let mut result = [0; 512];
+    let mut i = 0;
+    for (line_index, line) in lines.iter().enumerate() {
+        for c in line.chars() {
+            let bit = parse_pixel(c).ok_or_else(|| {
+                AdventError::parse(line_index + 1, line, &format!("bad pixel: {:?}", c))
+            })?;
+            if result.len() <= i {
+                return Err(Box::new(AdventError::parse(
+                    line_index + 1,
+                    line,
+                    "algorithm is longer than 512",
+                )));
+            }
+            result[i] = bit;
+            i += 1;
+        }
+    }
+    Ok(result)
+}
+
+#[test]
+fn test_parse_algorithm() {
+    let mut expected: Algorithm = [0; 512];
+    expected[1] = 1;
+    expected[3] = 1;
+    assert_eq!(expected, parse_algorithm(&[".#", ".#"]).unwrap());
+    assert!(parse_algorithm(&[".#", ".x"]).is_err());
+    assert!(parse_algorithm(&[&".".repeat(513)]).is_err());
+}
+
+/// Parses an image, which starts at line `first_line` of the input
+fn parse_image(lines: &[&str], first_line: usize) -> AdventResult<Image> {
+    let mut image = Image::new(0);
+    for (y, line) in lines.iter().enumerate() {
+        for (x, c) in line.chars().enumerate() {
+            match parse_pixel(c) {
+                Some(pixel) => image.set((x as i64, y as i64), pixel),
+                None => {
+                    return Err(Box::new(AdventError::parse(
+                        first_line + y,
+                        line,
+                        &format!("bad pixel: {:?}", c),
+                    )))
+                }
+            }
+        }
+    }
+    Ok(image)
+}
+
+#[test]
+fn test_parse_image() {
+    let mut expected = Image::new(0);
+    expected.set((1, 0), 1);
+    expected.set((1, 1), 1);
+    assert_eq!(expected, parse_image(&[".#", ".#"], 1).unwrap());
+    assert!(parse_image(&[".#", ".?"], 1).is_err());
+}
+
+/// The input is the algorithm, a blank line, and the image
+fn parse_input(lines: &[&str]) -> AdventResult<Input> {
+    let algorithm_size = lines.iter().take_while(|line| !line.is_empty()).count();
+    let algorithm_lines = &lines[..algorithm_size];
+    let bit_count: usize = algorithm_lines.iter().map(|line| line.len()).sum();
+    if bit_count != 512 {
+        return Err(Box::new(AdventError::new(&format!(
+            "algorithm has {} pixels, not 512",
+            bit_count
+        ))));
+    }
+    let algorithm = parse_algorithm(algorithm_lines)?;
+    let image_lines = lines.get(algorithm_size + 1..).unwrap_or(&[]);
+    if image_lines.is_empty() {
+        return Err(Box::new(AdventError::new(
+            "expected the algorithm, a blank line, and the image",
+        )));
+    }
+    let image = parse_image(image_lines, algorithm_size + 2)?;
+    Ok(Input { algorithm, image })
+}
+
+/// Returns the pixel if it's on in the new image.
+fn compute_one_pixel(p: SignedPoint, original: &Image, algorithm: &Algorithm) -> u8 {
+    let address: usize = neighbors(p).fold(0, |left, neighbor| {
+        (left << 1) + (*original.get(neighbor) as usize)
+    });
+    algorithm[address]
+}
+
+/// Runs one image processing step, producing a new image
+fn one_step(original: &Image, algorithm: &Algorithm) -> Image {
+    // Create a new image, and figure out what the background is.
+    let old_background_address = if *original.background() == 0 { 0 } else { 511 };
+    let new_background = algorithm[old_background_address];
+    let mut new_image = Image::new(new_background);
+
+    // Make the maximum bounds of the output image, which can be one pixel bigger
+    // along each edge.  An image that's all background stays that way.
+    let bounds = match original.bounds() {
+        Some(bounds) => bounds.expand(1),
+        None => return new_image,
+    };
+
+    // Check each possible pixel in the new image, and decide whether
+    // its on or not.
+    for p in bounds.points() {
+        new_image.set(p, compute_one_pixel(p, original, algorithm));
+    }
+
+    // all done
+    new_image
+}
+
+fn run_n_times(lines: &[&str], n: usize) -> AdventResult<Answer> {
+    let input = parse_input(lines)?;
+    let mut current_image = input.image.clone();
+    for _ in 0..n {
+        current_image = one_step(&current_image, &input.algorithm);
+    }
+    Ok(pixel_on_count(&current_image)?.into())
+}
+
+#[test]
+fn test_infinite_pixels() {
+    use crate::types::ErrorKind;
+
+    // Every pixel turns on, including the ones far away
+    let algorithm = "#".repeat(512);
+    let lines = [&algorithm[..], "", "#.", ".."];
+    let error = run_n_times(&lines, 2)
+        .unwrap_err()
+        .downcast::<AdventError>()
+        .unwrap();
+    assert_eq!(ErrorKind::NoSolution, error.kind());
+    assert_eq!(
+        "AdventError: no solution: infinitely many pixels are on",
+        error.to_string()
+    );
+}
+
+fn day_20_a(lines: &[&str]) -> AdventResult<Answer> {
+    run_n_times(lines, 2)
+}
+
+fn day_20_b(lines: &[&str]) -> AdventResult<Answer> {
+    run_n_times(lines, 50)
+}
+
+/// A random algorithm and image.  If the algorithm lights up dark pixels
+/// with no light around them, it has to turn off pixels with all light
+/// around them, so the number of lit pixels isn't infinite after two steps.
+fn generate(random: &mut Random, size: usize) -> Vec<String> {
+    let pixel = |random: &mut Random| if random.chance(0.5) { '#' } else { '.' };
+    let mut algorithm: Vec<char> = (0..512).map(|_| pixel(random)).collect();
+    if algorithm[0] == '#' {
+        algorithm[511] = '.';
+    }
+    let mut result = vec![algorithm.into_iter().collect(), "".to_string()];
+    for _ in 0..size {
+        result.push((0..size).map(|_| pixel(random)).collect());
+    }
+    result
+}
+
+pub fn make_day_20() -> Day {
+    Day::new(2021, 20, DayPart::new(day_20_a), DayPart::new(day_20_b)).with_generator(generate, 100)
+}
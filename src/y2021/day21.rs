@@ -1,7 +1,8 @@
 use std::cmp::max;
 use std::collections::HashMap;
 
-use crate::types::{AdventResult, Answer, Day, DayPart};
+use crate::generate::Random;
+use crate::types::{parse_lines, AdventError, AdventResult, Answer, Day, DayPart};
 
 /// Treats a range as a wraparound, and wraps
 /// to get the given number into the range.
@@ -62,9 +63,16 @@ impl Player {
         }
     }
 
-    fn from_input_line(line: &str) -> Player {
-        let pos = line.split_whitespace().last().unwrap().parse().unwrap();
-        Player::start(pos)
+    /// Parses a line like "Player 1 starting position: 4"
+    fn from_input_line(line: &str) -> AdventResult<Player> {
+        let pos: usize = match line.split_once("starting position: ") {
+            Some((_, pos)) => pos.parse()?,
+            None => return Err(Box::new(AdventError::new("expected a starting position"))),
+        };
+        if !(1..=10).contains(&pos) {
+            return Err(Box::new(AdventError::new("positions are 1 to 10")));
+        }
+        Ok(Player::start(pos))
     }
 
     fn one_move(&self, roll: usize) -> Player {
@@ -113,23 +121,42 @@ impl State {
     }
 }
 
-fn parse_input(lines: &[&str]) -> State {
-    let mut iter = lines.iter();
-    let players = [
-        Player::from_input_line(iter.next().unwrap()),
-        Player::from_input_line(iter.next().unwrap()),
-    ];
-    State { players, next: 0 }
+fn parse_input(lines: &[&str]) -> AdventResult<State> {
+    if lines.len() != 2 {
+        return Err(Box::new(AdventError::new("expected two players")));
+    }
+    let players = parse_lines(lines, 1, Player::from_input_line)?;
+    Ok(State {
+        players: [players[0].clone(), players[1].clone()],
+        next: 0,
+    })
+}
+
+#[test]
+fn test_parse_input() {
+    let state = parse_input(&[
+        "Player 1 starting position: 4",
+        "Player 2 starting position: 8",
+    ])
+    .unwrap();
+    assert_eq!([4, 8], state.players.map(|p| p.position));
+    assert!(parse_input(&["Player 1 starting position: 4"]).is_err());
+    assert!(parse_input(&[
+        "Player 1 starting position: 4",
+        "Player 2 starting position: 11",
+    ])
+    .is_err());
+    assert!(parse_input(&["Player 1 starting position: 4", "Player 2"]).is_err());
 }
 
 fn day_21_a(lines: &[&str]) -> AdventResult<Answer> {
     let winning_score = 1000;
-    let mut state = parse_input(lines);
+    let mut state = parse_input(lines)?;
     let mut die = Die::new();
     while !state.game_over(winning_score) {
         state = state.one_move(die.roll3());
     }
-    Ok((state.loser_score() * die.count) as Answer)
+    Ok((state.loser_score() * die.count).into())
 }
 
 fn day_21_b(lines: &[&str]) -> AdventResult<Answer> {
@@ -141,7 +168,7 @@ fn day_21_b(lines: &[&str]) -> AdventResult<Answer> {
     // Mapping from state to the number of universes that have that state
     // We start off with a single universe holding the initial state.
     let mut state_to_universes: HashMap<State, usize> = HashMap::new();
-    state_to_universes.insert(parse_input(lines), 1);
+    state_to_universes.insert(parse_input(lines)?, 1);
 
     // Run until all universes have complete games.
     loop {
@@ -177,15 +204,23 @@ fn day_21_b(lines: &[&str]) -> AdventResult<Answer> {
             player_2_wins += universe_count;
         }
     }
-    println!("win counts: {:?} {:?}", player_1_wins, player_2_wins);
+    eprintln!("win counts: {:?} {:?}", player_1_wins, player_2_wins);
+
+    Ok(max(player_1_wins, player_2_wins).into())
+}
 
-    Ok(max(player_1_wins, player_2_wins) as Answer)
+fn generate(random: &mut Random, _size: usize) -> Vec<String> {
+    (1..=2)
+        .map(|player| {
+            format!(
+                "Player {} starting position: {}",
+                player,
+                random.range(1..=10)
+            )
+        })
+        .collect()
 }
 
 pub fn make_day_21() -> Day {
-    Day::new(
-        21,
-        DayPart::new(day_21_a, 739785, 805932),
-        DayPart::new(day_21_b, 444356092776315, 133029050096658),
-    )
+    Day::new(2021, 21, DayPart::new(day_21_a), DayPart::new(day_21_b)).with_generator(generate, 1)
 }
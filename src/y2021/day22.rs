@@ -0,0 +1,301 @@
+use std::cmp::{max, min};
+use std::collections::HashMap;
+
+use crate::generate::Random;
+use crate::types::{parse_lines, AdventError, AdventResult, Answer, Day, DayPart};
+
+/// An inclusive span on one axis.  First number
+/// is always lower than second number.
+type Span = (i32, i32);
+
+/// How many numbers in a span?
+fn span_size(span: Span) -> i64 {
+    (span.1 as i64) - (span.0 as i64) + 1
+}
+
+/// Computes the intersection of two spans.
+fn intersect_spans(a: Span, b: Span) -> Option<Span> {
+    let low = max(a.0, b.0);
+    let high = min(a.1, b.1);
+    if low <= high {
+        Some((low, high))
+    } else {
+        None
+    }
+}
+
+#[test]
+fn test_intersect_spans() {
+    assert_eq!(None, intersect_spans((1, 4), (5, 9)));
+    assert_eq!(Some((5, 5)), intersect_spans((1, 5), (5, 9)));
+    assert_eq!(Some((4, 6)), intersect_spans((1, 10), (4, 6)));
+}
+
+/// A cube
+type Cube = (Span, Span, Span);
+
+/// How many cubelets in a cube?
+fn cube_size(cube: Cube) -> i64 {
+    let (x, y, z) = cube;
+    span_size(x) * span_size(y) * span_size(z)
+}
+
+/// Computes the intersection of two cubes
+fn intersect_cubes(a: Cube, b: Cube) -> Option<Cube> {
+    let (a_x, a_y, a_z) = a;
+    let (b_x, b_y, b_z) = b;
+    if let Some(x) = intersect_spans(a_x, b_x) {
+        if let Some(y) = intersect_spans(a_y, b_y) {
+            if let Some(z) = intersect_spans(a_z, b_z) {
+                return Some((x, y, z));
+            }
+        }
+    }
+    None
+}
+
+#[test]
+fn test_intersect_cubes() {
+    let ten = ((1, 10), (1, 10), (1, 10));
+    let lower = ((1, 2), (2, 3), (3, 4));
+    let middle = ((4, 6), (4, 6), (4, 6));
+    assert_eq!(None, intersect_cubes(lower, middle));
+    assert_eq!(Some(middle), intersect_cubes(middle, ten));
+}
+
+/// Parses a span like "x=-20..26"
+fn parse_span(s: &str, axis: &str) -> AdventResult<Span> {
+    let numbers = s
+        .strip_prefix(axis)
+        .and_then(|s| s.strip_prefix('='))
+        .and_then(|s| s.split_once(".."))
+        .ok_or_else(|| AdventError::new(&format!("expected a span like {}=-20..26", axis)))?;
+    let low = numbers.0.parse()?;
+    let high = numbers.1.parse()?;
+    if high < low {
+        return Err(Box::new(AdventError::new(&format!(
+            "backwards span: {}",
+            s
+        ))));
+    }
+    Ok((low, high))
+}
+
+fn parse_line(line: &str) -> AdventResult<(bool, Cube)> {
+    let (on_off, spans) = line
+        .split_once(' ')
+        .ok_or_else(|| AdventError::new("expected on or off, and then the cube"))?;
+    let is_on = match on_off {
+        "on" => true,
+        "off" => false,
+        _ => return Err(Box::new(AdventError::new("expected on or off"))),
+    };
+    let spans: Vec<&str> = spans.split(",").collect();
+    if spans.len() != 3 {
+        return Err(Box::new(AdventError::new("expected x, y, and z spans")));
+    }
+    let x = parse_span(spans[0], "x")?;
+    let y = parse_span(spans[1], "y")?;
+    let z = parse_span(spans[2], "z")?;
+    Ok((is_on, (x, y, z)))
+}
+
+#[test]
+fn test_parse_line() {
+    assert_eq!(
+        (true, ((-20, 26), (-36, 17), (-47, 7))),
+        parse_line("on x=-20..26,y=-36..17,z=-47..7").unwrap()
+    );
+    assert_eq!(
+        (false, ((-48, -32), (-32, -16), (-15, -5))),
+        parse_line("off x=-48..-32,y=-32..-16,z=-15..-5").unwrap()
+    );
+    assert!(parse_line("of x=-48..-32,y=-32..-16,z=-15..-5").is_err());
+    assert!(parse_line("off x=-48..-32,y=-32..-16").is_err());
+    assert!(parse_line("off x=-48..-32,z=-32..-16,y=-15..-5").is_err());
+    assert!(parse_line("off x=-32..-48,y=-32..-16,z=-15..-5").is_err());
+    assert!(parse_line("off x=-48..-32,y=-32..-16,z=-15..q").is_err());
+}
+
+fn parse_input(lines: &[&str]) -> AdventResult<Vec<(bool, Cube)>> {
+    parse_lines(lines, 1, parse_line)
+}
+
+/// Builds a new cube-to-coefficient mapping that is the result of adding a
+/// new instruction to an existing mapping.
+fn add_one_instruction(
+    instruction: (bool, Cube),
+    before: &HashMap<Cube, i64>,
+) -> HashMap<Cube, i64> {
+    let (is_on, new_cube) = instruction;
+
+    // The result starts out the same as the previous mapping.
+    let mut result = before.clone();
+
+    // The first step is to undo anything that affects the cube being added.
+    for (old_cube, old_coefficient) in before {
+        if let Some(intersection) = intersect_cubes(new_cube, *old_cube) {
+            *result.entry(intersection).or_insert(0) -= old_coefficient;
+        }
+    }
+
+    // Everything in the span of the new cube has been zeroed out now.
+    // If we're turning this cube off, then we're done.
+    // If we're turning it on, then do so.
+    if is_on {
+        *result.entry(new_cube).or_insert(0) += 1;
+    }
+
+    result
+}
+
+fn count_cubelets(cube_to_coefficient: &HashMap<Cube, i64>) -> usize {
+    let mut result: i64 = 0;
+    for (cube, coefficient) in cube_to_coefficient {
+        result += coefficient * cube_size(*cube);
+    }
+    result as usize
+}
+
+#[test]
+fn test_part_a() {
+    let mut result: HashMap<Cube, i64> = HashMap::new();
+    result = add_one_instruction(
+        parse_line("on x=10..12,y=10..12,z=10..12").unwrap(),
+        &result,
+    );
+    assert_eq!(27, count_cubelets(&result));
+    result = add_one_instruction(
+        parse_line("on x=11..13,y=11..13,z=11..13").unwrap(),
+        &result,
+    );
+    assert_eq!(27 + 19, count_cubelets(&result));
+    result = add_one_instruction(parse_line("off x=9..11,y=9..11,z=9..11").unwrap(), &result);
+    assert_eq!(27 + 19 - 8, count_cubelets(&result));
+    result = add_one_instruction(
+        parse_line("on x=10..10,y=10..10,z=10..10").unwrap(),
+        &result,
+    );
+    assert_eq!(39, count_cubelets(&result));
+}
+
+fn day_22_a(lines: &[&str]) -> AdventResult<Answer> {
+    let mut result: HashMap<Cube, i64> = HashMap::new();
+    for (is_on, cube_from_line) in parse_input(lines)? {
+        if let Some(cube_to_use) =
+            intersect_cubes(cube_from_line, ((-50, 50), (-50, 50), (-50, 50)))
+        {
+            result = add_one_instruction((is_on, cube_to_use), &result);
+        }
+    }
+    Ok(count_cubelets(&result).into())
+}
+
+fn day_22_b(lines: &[&str]) -> AdventResult<Answer> {
+    let mut result: HashMap<Cube, i64> = HashMap::new();
+    for instruction in parse_input(lines)? {
+        result = add_one_instruction(instruction, &result);
+    }
+
+    eprintln!("length: {:?}", result.len());
+    Ok(count_cubelets(&result).into())
+}
+
+/// Turns every cubelet in the middle on and off, one at a time
+fn reference_a(lines: &[&str]) -> AdventResult<Answer> {
+    let index = |n: i32| (n + 50) as usize;
+    let mut is_on = vec![false; 101 * 101 * 101];
+    for (on, cube) in parse_input(lines)? {
+        if let Some((x, y, z)) = intersect_cubes(cube, ((-50, 50), (-50, 50), (-50, 50))) {
+            for i in index(x.0)..=index(x.1) {
+                for j in index(y.0)..=index(y.1) {
+                    for k in index(z.0)..=index(z.1) {
+                        is_on[(i * 101 + j) * 101 + k] = on;
+                    }
+                }
+            }
+        }
+    }
+    Ok(is_on.iter().filter(|&&on| on).count().into())
+}
+
+/// Cuts space into boxes at every edge of every cuboid, and turns whole
+/// boxes on and off, one at a time
+fn reference_b(lines: &[&str]) -> AdventResult<Answer> {
+    let steps = parse_input(lines)?;
+    let edges = |axis: fn(&Cube) -> Span| {
+        let mut edges: Vec<i64> = steps
+            .iter()
+            .flat_map(|(_, cube)| [axis(cube).0 as i64, axis(cube).1 as i64 + 1])
+            .collect();
+        edges.sort();
+        edges.dedup();
+        edges
+    };
+    let xs = edges(|cube| cube.0);
+    let ys = edges(|cube| cube.1);
+    let zs = edges(|cube| cube.2);
+    let boxes = |edges: &[i64], span: Span| {
+        let start = edges.binary_search(&(span.0 as i64)).unwrap();
+        let end = edges.binary_search(&(span.1 as i64 + 1)).unwrap();
+        start..end
+    };
+    let mut is_on = vec![false; xs.len() * ys.len() * zs.len()];
+    for (on, (x, y, z)) in steps.iter() {
+        for i in boxes(&xs, *x) {
+            for j in boxes(&ys, *y) {
+                for k in boxes(&zs, *z) {
+                    is_on[(i * ys.len() + j) * zs.len() + k] = *on;
+                }
+            }
+        }
+    }
+    let mut count: i64 = 0;
+    for i in 0..xs.len() - 1 {
+        for j in 0..ys.len() - 1 {
+            for k in 0..zs.len() - 1 {
+                if is_on[(i * ys.len() + j) * zs.len() + k] {
+                    count += (xs[i + 1] - xs[i]) * (ys[j + 1] - ys[j]) * (zs[k + 1] - zs[k]);
+                }
+            }
+        }
+    }
+    Ok((count as u64).into())
+}
+
+/// Steps that start with cuboids near the middle, for part A, and then go
+/// on to big ones, out to about `size` thousand in each direction
+fn generate(random: &mut Random, size: usize) -> Vec<String> {
+    let span = |random: &mut Random, axis: char, reach: i64, length: i64| {
+        let start = random.range(-reach..=reach - length);
+        let end = start + random.range(length / 2..=length);
+        format!("{}={}..{}", axis, start, end)
+    };
+    (0..size)
+        .map(|i| {
+            let on_off = if i < 10 || random.chance(0.5) {
+                "on"
+            } else {
+                "off"
+            };
+            let (reach, length) = if i < 20 {
+                (50, 50)
+            } else {
+                (1000 * size as i64, 40 * size as i64)
+            };
+            format!(
+                "{} {},{},{}",
+                on_off,
+                span(random, 'x', reach, length),
+                span(random, 'y', reach, length),
+                span(random, 'z', reach, length)
+            )
+        })
+        .collect()
+}
+
+pub fn make_day_22() -> Day {
+    Day::new(2021, 22, DayPart::new(day_22_a), DayPart::new(day_22_b))
+        .with_generator(generate, 420)
+        .with_reference(Some(reference_a), Some(reference_b), 25)
+}
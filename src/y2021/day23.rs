@@ -12,12 +12,11 @@
 // room and not blocking anybody.
 //
 
-use crate::types::{AdventResult, Answer, Day, DayPart};
+use crate::generate::Random;
+use crate::grid::{Grid, Point};
+use crate::types::{AdventError, AdventResult, Answer, Day, DayPart};
 
-use ndarray::{Array2, ArrayBase};
-
-type State = Array2<u8>;
-type Point = (usize, usize);
+type State = Grid<u8>;
 
 /// Information about the problem.
 ///
@@ -57,29 +56,61 @@ fn is_amphipod(c: u8) -> bool {
     }
 }
 
-fn parse_state(lines: &[&str]) -> State {
+/// Parses the map of the burrow.  The top wall is the widest line.
+fn parse_state(lines: &[&str]) -> AdventResult<State> {
+    if lines.len() < 4 {
+        return Err(Box::new(AdventError::new(
+            "expected a hall, at least one row of rooms, and walls",
+        )));
+    }
     let width = lines[0].as_bytes().len();
     let height = lines.len();
-    let mut result = ArrayBase::zeros((width, height));
     for (y, line) in lines.iter().enumerate() {
-        for (x, c) in line.as_bytes().iter().enumerate() {
-            result[(x, y)] = *c;
+        if width < line.len() {
+            return Err(Box::new(AdventError::parse(
+                y + 1,
+                line,
+                "line is wider than the top wall",
+            )));
+        }
+        for c in line.as_bytes() {
+            if !is_amphipod(*c) && !b"#. ".contains(c) {
+                return Err(Box::new(AdventError::parse(
+                    y + 1,
+                    line,
+                    &format!("unexpected character: {:?}", *c as char),
+                )));
+            }
         }
     }
-    result
+    // Lines shorter than the top wall have nothing past their ends
+    Ok(Grid::from_fn((width, height), |(x, y)| {
+        lines[y].as_bytes().get(x).copied().unwrap_or(b' ')
+    }))
+}
+
+#[test]
+fn test_parse_state() {
+    assert!(parse_state(&["#####", "#...#", "###A#", "  #B#", "  ###"]).is_ok());
+    assert_eq!(
+        "AdventError: parse error: line 3: \"###E#\": unexpected character: 'E'",
+        parse_state(&["#####", "#...#", "###E#", "  ###"])
+            .unwrap_err()
+            .to_string()
+    );
+    assert!(parse_state(&["#####", "#...#", "###A##", "  ###"]).is_err());
+    assert!(parse_state(&["#####", "#...#"]).is_err());
 }
 
 fn print_state(state: &State) {
-    let shape = state.shape();
-    let width = shape[0];
-    let height = shape[1];
+    let (width, height) = state.shape();
     for y in 0..height {
         for x in 0..width {
-            print!("{}", state[(x, y)] as char)
+            eprint!("{}", state[(x, y)] as char)
         }
-        println!("");
+        eprintln!();
     }
-    println!("");
+    eprintln!();
 }
 
 /// Are all the right amphipods in the room?
@@ -151,9 +182,7 @@ impl Move {
 }
 
 fn get_info(state: &State) -> Info {
-    let shape = state.shape();
-    let width = shape[0];
-    let height = shape[1];
+    let (width, height) = state.shape();
     let room_xs: Vec<_> = (0..width).filter(|x| state[(*x, 2)] != b'#').collect();
     let hall_seat_xs = (0..width)
         .filter(|x| state[(*x, 1)] != b'#')
@@ -279,78 +308,114 @@ fn search(state: &mut State) -> Option<usize> {
 fn test_search() {
     assert_eq!(
         Some(0),
-        search(&mut parse_state(&[
-            "#############",
-            "#...........#",
-            "###A#B#C#D###",
-            "  #A#B#C#D#",
-            "  #########",
-        ]))
+        search(
+            &mut parse_state(&[
+                "#############",
+                "#...........#",
+                "###A#B#C#D###",
+                "  #A#B#C#D#",
+                "  #########",
+            ])
+            .unwrap()
+        )
     );
 
     assert_eq!(
         Some(8),
-        search(&mut parse_state(&[
-            "#############",
-            "#.........A.#",
-            "###.#B#C#D###",
-            "  #A#B#C#D#",
-            "  #########",
-        ]))
+        search(
+            &mut parse_state(&[
+                "#############",
+                "#.........A.#",
+                "###.#B#C#D###",
+                "  #A#B#C#D#",
+                "  #########",
+            ])
+            .unwrap()
+        )
     );
 
     assert_eq!(
         Some(4008),
-        search(&mut parse_state(&[
-            "#############",
-            "#.....D...A.#",
-            "###.#B#C#.###",
-            "  #A#B#C#D#",
-            "  #########",
-        ]))
+        search(
+            &mut parse_state(&[
+                "#############",
+                "#.....D...A.#",
+                "###.#B#C#.###",
+                "  #A#B#C#D#",
+                "  #########",
+            ])
+            .unwrap()
+        )
     );
 
     assert_eq!(
         Some(7008),
-        search(&mut parse_state(&[
-            "#############",
-            "#.....D.D.A.#",
-            "###.#B#C#.###",
-            "  #A#B#C#.#",
-            "  #########",
-        ]))
+        search(
+            &mut parse_state(&[
+                "#############",
+                "#.....D.D.A.#",
+                "###.#B#C#.###",
+                "  #A#B#C#.#",
+                "  #########",
+            ])
+            .unwrap()
+        )
     );
 
     assert_eq!(
         Some(7011),
-        search(&mut parse_state(&[
-            "#############",
-            "#.....D.D...#",
-            "###.#B#C#.###",
-            "  #A#B#C#A#",
-            "  #########",
-        ]))
+        search(
+            &mut parse_state(&[
+                "#############",
+                "#.....D.D...#",
+                "###.#B#C#.###",
+                "  #A#B#C#A#",
+                "  #########",
+            ])
+            .unwrap()
+        )
     );
 
     assert_eq!(
         Some(9011),
-        search(&mut parse_state(&[
-            "#############",
-            "#.....D.....#",
-            "###.#B#C#D###",
-            "  #A#B#C#A#",
-            "  #########",
-        ]))
+        search(
+            &mut parse_state(&[
+                "#############",
+                "#.....D.....#",
+                "###.#B#C#D###",
+                "  #A#B#C#A#",
+                "  #########",
+            ])
+            .unwrap()
+        )
     );
 }
 
+/// Finds the lowest cost, if there's a way to sort the amphipods
+fn solve(state: &mut State) -> AdventResult<Answer> {
+    match search(state) {
+        Some(cost) => Ok(cost.into()),
+        None => Err(Box::new(AdventError::no_solution(
+            "the amphipods can't be sorted",
+        ))),
+    }
+}
+
 fn day_23_a(lines: &[&str]) -> AdventResult<Answer> {
-    let mut state = parse_state(lines);
+    let mut state = parse_state(lines)?;
     print_state(&state);
-    Ok(search(&mut state).unwrap() as Answer)
+    solve(&mut state)
 }
 
-fn day_23_b(lines: &[&str]) -> AdventResult<Answer> {
+/// Adds the two rows of rooms that were folded up in part A
+fn unfold<'a>(lines: &[&'a str]) -> AdventResult<Vec<&'a str>> {
+    // Check the input before unfolding it, so problems are on the right lines
+    parse_state(lines)?;
+    if lines.len() != 5 {
+        return Err(Box::new(AdventError::new(
+            "expected one row of rooms to unfold",
+        )));
+    }
     let mut updated_lines = Vec::new();
     updated_lines.push(lines[0]);
     updated_lines.push(lines[1]);
@@ -359,15 +424,41 @@ fn day_23_b(lines: &[&str]) -> AdventResult<Answer> {
     updated_lines.push("  #D#B#A#C#");
     updated_lines.push(lines[3]);
     updated_lines.push(lines[4]);
-    let mut state = parse_state(&updated_lines[..]);
+    Ok(updated_lines)
+}
+
+fn day_23_b(lines: &[&str]) -> AdventResult<Answer> {
+    let mut state = parse_state(&unfold(lines)?)?;
     print_state(&state);
-    Ok(search(&mut state).unwrap() as Answer)
+    solve(&mut state)
+}
+
+/// The amphipods are mixed up in the rooms.  Some ways of mixing them up
+/// can't be sorted once the rooms are unfolded, so random ones are tried
+/// until both parts can be.
+fn generate(random: &mut Random, _size: usize) -> Vec<String> {
+    let row = |amphipods: &[char]| {
+        let letters: Vec<String> = amphipods.iter().map(|c| c.to_string()).collect();
+        letters.join("#")
+    };
+    loop {
+        let mut amphipods: Vec<char> = "AABBCCDD".chars().collect();
+        random.shuffle(&mut amphipods);
+        let lines = vec![
+            "#############".to_string(),
+            "#...........#".to_string(),
+            format!("###{}###", row(&amphipods[..4])),
+            format!("  #{}#", row(&amphipods[4..])),
+            "  #########".to_string(),
+        ];
+        let strs: Vec<&str> = lines.iter().map(|s| &s[..]).collect();
+        let can_sort = |lines: &[&str]| search(&mut parse_state(lines).unwrap()).is_some();
+        if can_sort(&strs) && can_sort(&unfold(&strs).unwrap()) {
+            return lines;
+        }
+    }
 }
 
 pub fn make_day_23() -> Day {
-    Day::new(
-        23,
-        DayPart::new(day_23_a, 12521, 17400),
-        DayPart::new(day_23_b, 44169, 46120),
-    )
+    Day::new(2021, 23, DayPart::new(day_23_a), DayPart::new(day_23_b)).with_generator(generate, 1)
 }
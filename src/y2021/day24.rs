@@ -3,9 +3,10 @@ use std::fmt;
 use std::ops;
 use std::rc::Rc;
 
-use crate::day24_alu::{InputName, Instruction, OpName, RegisterName, RegisterOrConstant};
-use crate::types::{AdventResult, Answer, Day, DayPart};
+use crate::generate::Random;
+use crate::types::{parse_lines, AdventError, AdventResult, Answer, Day, DayPart};
 use crate::value_range::ValueRange;
+use crate::y2021::day24_alu::{InputName, Instruction, OpName, RegisterName, RegisterOrConstant};
 
 use Instruction::*;
 use OpName::*;
@@ -242,7 +243,7 @@ fn simplify_in_mod_helper(expr: &Expr, modulus: i64) -> Option<Expr> {
         ExprDetails::Poly(polynomial) => {
             if let Some(n) = polynomial.get_constant() {
                 if n % modulus != n {
-                    println!("    => {:?}", n % modulus);
+                    eprintln!("    => {:?}", n % modulus);
                     Some(Expr::constant(n % modulus))
                 } else {
                     None
@@ -593,7 +594,7 @@ impl State {
                 };
                 let mut expr = Expr::op(*op_name, lhs, rhs);
                 while let Some(simplified) = simplify(&expr) {
-                    println!("SIMPLIFY {:?} => {:?}", expr, simplified);
+                    eprintln!("SIMPLIFY {:?} => {:?}", expr, simplified);
                     expr = simplified;
                 }
                 State {
@@ -606,30 +607,29 @@ impl State {
 }
 
 fn print_state(state: &State) {
-    // println!("next input: {:?}", state.next_input);
     for (r, expr) in RegisterName::all().into_iter().zip(state.registers.iter()) {
-        println!("{:?} = {:?}   {:?}", r, expr.get_range(), *expr);
+        eprintln!("{:?} = {:?}   {:?}", r, expr.get_range(), *expr);
     }
-    println!("");
+    eprintln!();
 }
 
 fn indent(indentation: usize) {
     for _ in 0..indentation {
-        print!("  ");
+        eprint!("  ");
     }
 }
 
 fn print_tree(expr: &Expr, indentation: usize) {
     let range = expr.get_range();
     match expr.details() {
-        ExprDetails::Poly(polynomial) => println!(
+        ExprDetails::Poly(polynomial) => eprintln!(
             "{:?} {{{:?} .. {:?}}}",
             polynomial,
             range.start(),
             range.end()
         ),
         ExprDetails::Op(op_name, lhs, rhs) => {
-            println!("{:?} {{{:?} .. {:?}}}", op_name, range.start(), range.end());
+            eprintln!("{:?} {{{:?} .. {:?}}}", op_name, range.start(), range.end());
             indent(indentation + 1);
             print_tree(lhs, indentation + 1);
             indent(indentation + 1);
@@ -663,7 +663,7 @@ fn evaluate_instructions(instructions: &[Instruction], inputs: &[i64; 14]) -> i6
 fn evaluate_one(instructions: &[Instruction], z_expr: &Expr, inputs: &[i64; 14]) {
     let from_instructions = evaluate_instructions(instructions, inputs);
     let from_simplified = z_expr.evaluate(inputs);
-    println!(
+    eprintln!(
         "EVAL: {:?} => {:?} {:?}",
         inputs, from_instructions, from_simplified
     );
@@ -676,13 +676,13 @@ fn day_24_a_old(lines: &[&str]) -> AdventResult<Answer> {
     let mut state = State::start();
     let mut instructions = Vec::new();
     for line in lines {
-        println!("INSTRUCTION: {:?}\n", line);
+        eprintln!("INSTRUCTION: {:?}\n", line);
         let instruction = line.parse().unwrap();
         state = state.after(&instruction);
         instructions.push(instruction);
         print_state(&state);
     }
-    println!("\n\n\n\n\n\n");
+    eprintln!("\n\n\n\n\n\n");
     let z_expr = &state.registers[3];
     print_tree(z_expr, 0);
     for n in 0..=8 {
@@ -693,7 +693,7 @@ fn day_24_a_old(lines: &[&str]) -> AdventResult<Answer> {
         }
     }
     evaluate_one(&instructions[..], z_expr, &[9; 14]);
-    Ok(0)
+    Ok(0u64.into())
 }
 
 type Ranges = [ValueRange; 4];
@@ -776,7 +776,7 @@ fn search(
     bindings: [i64; 14],
     input_counter: usize,
     search_order: &[i64],
-) -> Option<Answer> {
+) -> Option<u64> {
     // Run instructions as long they are not input instructions, and we're not at the end.
     for pc in starting_pc..infos.len() {
         match &infos[pc].instruction {
@@ -825,26 +825,30 @@ fn search(
         for b in bindings {
             result = result * 10 + b;
         }
-        Some(result as Answer)
+        Some(result as u64)
     } else {
         // not zero; need caller to keep searching
         None
     }
 }
 
+fn parse_input(lines: &[&str]) -> AdventResult<Vec<Instruction>> {
+    parse_lines(lines, 1, |line| Ok(line.parse()?))
+}
+
 fn day_24(lines: &[&str], search_order: &[i64]) -> AdventResult<Answer> {
     if false {
         let _ = day_24_a_old(lines);
     }
 
+    let instructions = parse_input(lines)?;
     let mut infos = Vec::new();
 
     // Collect the instructions, and calculate the possible value ranges
     // for each
     {
         let mut ranges = [ValueRange::new(0, 0); 4];
-        for line in lines {
-            let instruction = line.parse().unwrap();
+        for instruction in instructions {
             ranges = ranges_after(&ranges, &instruction);
             let limits = [None; 4];
             let info = Info {
@@ -928,7 +932,12 @@ fn day_24(lines: &[&str], search_order: &[i64]) -> AdventResult<Answer> {
     }
 
     // Now do the search
-    Ok(search([0; 4], &infos, 0, [0; 14], 0, search_order).unwrap())
+    match search([0; 4], &infos, 0, [0; 14], 0, search_order) {
+        Some(model_number) => Ok(model_number.into()),
+        None => Err(Box::new(AdventError::no_solution(
+            "no model number leaves z at 0",
+        ))),
+    }
 }
 
 fn day_24_a(lines: &[&str]) -> AdventResult<Answer> {
@@ -941,10 +950,42 @@ fn day_24_b(lines: &[&str]) -> AdventResult<Answer> {
     day_24(lines, &search_order[..])
 }
 
+/// A MONAD program like the real ones: 14 blocks that each read a digit,
+/// where half of them push a number onto a stack kept in z, and half pop
+/// one off, if the digit read matches it.  Each pop is paired with a push,
+/// and the digits for each pair can always match.
+fn generate(random: &mut Random, _size: usize) -> Vec<String> {
+    let mut pushes_left = 7;
+    let mut stack: Vec<i64> = Vec::new();
+    let mut result = Vec::new();
+    for _ in 0..14 {
+        let push = if stack.is_empty() {
+            true
+        } else if pushes_left == 0 {
+            false
+        } else {
+            random.chance(0.5)
+        };
+        let (divisor, x_offset, y_offset) = if push {
+            pushes_left -= 1;
+            let y_offset = random.range(1..=16);
+            stack.push(y_offset);
+            (1, random.range(10..=15), y_offset)
+        } else {
+            // The digit read has to be this far from the pushed digit
+            let difference = random.range(-8..=8);
+            (26, difference - stack.pop().unwrap(), random.range(1..=16))
+        };
+        let block = format!(
+            "inp w/mul x 0/add x z/mod x 26/div z {}/add x {}/eql x w/eql x 0/mul y 0/add y 25/\
+             mul y x/add y 1/mul z y/mul y 0/add y w/add y {}/mul y x/add z y",
+            divisor, x_offset, y_offset
+        );
+        result.extend(block.split('/').map(|s| s.to_string()));
+    }
+    result
+}
+
 pub fn make_day_24() -> Day {
-    Day::new(
-        24,
-        DayPart::new(day_24_a, 80000000000000, 12996997829399),
-        DayPart::new(day_24_b, 20000000000000, 11841231117189),
-    )
+    Day::new(2021, 24, DayPart::new(day_24_a), DayPart::new(day_24_b)).with_generator(generate, 1)
 }
@@ -1,17 +1,11 @@
 use std::fmt;
 use std::str;
 
+#[cfg(test)]
+use crate::types::parse_lines;
+use crate::types::{AdventError, ErrorKind};
 use crate::value_range::ValueRange;
 
-/// Error type used for a variety of things in this day24_alu module.
-#[derive(Debug, Eq, PartialEq)]
-pub enum AluError {
-    BadRegisterName(String),
-    NotRegisterOrConstant(String),
-    NotOpName(String),
-    BadInstruction(String),
-}
-
 /// The name of a register in the ALU
 ///
 /// Registers are named 'w' through 'z'
@@ -43,15 +37,18 @@ impl fmt::Debug for RegisterName {
 }
 
 impl str::FromStr for RegisterName {
-    type Err = AluError;
+    type Err = AdventError;
 
-    fn from_str(s: &str) -> Result<RegisterName, AluError> {
+    fn from_str(s: &str) -> Result<RegisterName, AdventError> {
+        // The line it's on is added by whatever parses the whole program
+        let bad_name =
+            || AdventError::of_kind(ErrorKind::Parse, &format!("bad register name: {:?}", s));
         if s.len() != 1 {
-            return Err(AluError::BadRegisterName(s.to_string()));
+            return Err(bad_name());
         }
         let name = s.chars().next().unwrap();
         if name < 'w' || 'z' < name {
-            return Err(AluError::BadRegisterName(s.to_string()));
+            return Err(bad_name());
         }
         Ok(RegisterName { name })
     }
@@ -60,17 +57,25 @@ impl str::FromStr for RegisterName {
 #[test]
 fn test_register_name() {
     assert_eq!(
-        AluError::BadRegisterName("bad".to_string()),
-        "bad".parse::<RegisterName>().err().unwrap()
+        "AdventError: parse error: bad register name: \"bad\"",
+        "bad".parse::<RegisterName>().err().unwrap().to_string()
     );
     assert_eq!(
-        AluError::BadRegisterName("m".to_string()),
-        "m".parse::<RegisterName>().err().unwrap()
+        "AdventError: parse error: bad register name: \"m\"",
+        "m".parse::<RegisterName>().err().unwrap().to_string()
     );
     assert_eq!(
         RegisterName { name: 'x' },
         "x".parse::<RegisterName>().unwrap()
     );
+
+    let program = parse_lines(&["inp w", "add m 1"], 1, |line| {
+        Ok(line.parse::<Instruction>()?)
+    });
+    assert_eq!(
+        "AdventError: parse error: line 2: \"add m 1\": bad register name: \"m\"",
+        program.unwrap_err().to_string()
+    );
 }
 
 /// The name of one of the inputs
@@ -139,14 +144,17 @@ impl fmt::Debug for RegisterOrConstant {
 }
 
 impl str::FromStr for RegisterOrConstant {
-    type Err = AluError;
-    fn from_str(s: &str) -> Result<RegisterOrConstant, AluError> {
+    type Err = AdventError;
+    fn from_str(s: &str) -> Result<RegisterOrConstant, AdventError> {
         if let Ok(register_name) = s.parse::<RegisterName>() {
             Ok(Register(register_name))
         } else if let Ok(n) = s.parse::<i64>() {
             Ok(Constant(n))
         } else {
-            Err(AluError::NotRegisterOrConstant(s.to_string()))
+            Err(AdventError::new(&format!(
+                "not a register or constant: {:?}",
+                s
+            )))
         }
     }
 }
@@ -219,15 +227,15 @@ impl fmt::Debug for OpName {
 }
 
 impl str::FromStr for OpName {
-    type Err = AluError;
-    fn from_str(s: &str) -> Result<OpName, AluError> {
+    type Err = AdventError;
+    fn from_str(s: &str) -> Result<OpName, AdventError> {
         match s {
             "add" => Ok(Add),
             "mul" => Ok(Mul),
             "div" => Ok(Div),
             "mod" => Ok(Mod),
             "eql" => Ok(Eql),
-            _ => Err(AluError::NotOpName(s.to_string())),
+            _ => Err(AdventError::new(&format!("not an operation: {:?}", s))),
         }
     }
 }
@@ -255,18 +263,20 @@ pub enum Instruction {
 use Instruction::*;
 
 impl str::FromStr for Instruction {
-    type Err = AluError;
-    fn from_str(s: &str) -> Result<Instruction, AluError> {
+    type Err = AdventError;
+    fn from_str(s: &str) -> Result<Instruction, AdventError> {
         let words: Vec<_> = s.split_whitespace().collect();
-        if words[0] == "inp" {
+        if words.is_empty() {
+            Err(AdventError::new(&format!("bad instruction: {:?}", s)))
+        } else if words[0] == "inp" {
             if words.len() != 2 {
-                Err(AluError::BadInstruction(s.to_string()))
+                Err(AdventError::new(&format!("bad instruction: {:?}", s)))
             } else {
                 Ok(Inp(words[1].parse()?))
             }
         } else {
             if words.len() != 3 {
-                Err(AluError::BadInstruction(s.to_string()))
+                Err(AdventError::new(&format!("bad instruction: {:?}", s)))
             } else {
                 Ok(Op(words[0].parse()?, words[1].parse()?, words[2].parse()?))
             }
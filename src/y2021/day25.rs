@@ -0,0 +1,109 @@
+use crate::generate::Random;
+use crate::grid::{check_grid, Direction, Grid, Topology};
+use crate::types::{AdventError, AdventResult, Answer, Day, DayPart};
+
+fn parse_input(lines: &[&str]) -> AdventResult<Grid> {
+    if lines.is_empty() || lines[0].is_empty() {
+        return Err(Box::new(AdventError::new("the sea floor is empty")));
+    }
+    // Sea cucumbers that go off one edge come back on the other
+    let grid = Grid::from_chars(
+        lines,
+        |c| ">v.".contains(c).then_some(c as u8),
+        "'>', 'v', or '.'",
+    )?;
+    Ok(grid.with_topology(Topology::TORUS))
+}
+
+#[test]
+fn test_parse_input() {
+    assert_eq!((3, 2), parse_input(&[">.v", "..."]).unwrap().shape());
+    assert_eq!(
+        "AdventError: parse error: line 2: \"..\": expected 3 cells, like the first row",
+        parse_input(&[">.v", ".."]).unwrap_err().to_string()
+    );
+    assert!(parse_input(&[">.x"]).is_err());
+    assert!(parse_input(&[]).is_err());
+}
+
+/// Moves every sea cucumber in one herd that has an empty space in front
+/// of it, all at the same time
+fn move_herd(grid: &Grid, herd: u8, direction: Direction) -> Grid {
+    let neighbor = |grid: &Grid, pos, direction| grid[grid.step(pos, direction).unwrap()];
+    grid.next_generation(|grid, pos| match grid[pos] {
+        cell if cell == herd && neighbor(grid, pos, direction) == b'.' => b'.',
+        b'.' if neighbor(grid, pos, direction.opposite()) == herd => herd,
+        cell => cell,
+    })
+}
+
+/// First, the east herd moves, and then the south herd
+fn one_step(grid: &Grid) -> Grid {
+    let moved_east = move_herd(grid, b'>', Direction::Right);
+    move_herd(&moved_east, b'v', Direction::Down)
+}
+
+#[test]
+fn test_one_step() {
+    let grid = parse_input(&["...>>>>>..."]).unwrap();
+    let grid = one_step(&grid);
+    assert_eq!(parse_input(&["...>>>>.>.."]).unwrap(), grid);
+    assert_eq!(parse_input(&["...>>>.>.>."]).unwrap(), one_step(&grid));
+
+    // Both herds wrap around
+    let grid = parse_input(&["..>", "v..", "..."]).unwrap();
+    assert_eq!(
+        parse_input(&[">..", "...", "v.."]).unwrap(),
+        one_step(&grid)
+    );
+    let grid = parse_input(&["...", "v..", "v.."]).unwrap();
+    assert_eq!(
+        parse_input(&["v..", "v..", "..."]).unwrap(),
+        one_step(&grid)
+    );
+}
+
+fn day_25_a(lines: &[&str]) -> AdventResult<Answer> {
+    let grid = parse_input(lines)?;
+    match grid.run_until_stable(usize::MAX, one_step) {
+        Some((_, steps)) => Ok(steps.into()),
+        None => Err(Box::new(AdventError::no_solution(
+            "the sea cucumbers never stop",
+        ))),
+    }
+}
+
+fn day_25_b(_lines: &[&str]) -> AdventResult<Answer> {
+    Ok(0u64.into())
+}
+
+/// The sea floor must be a rectangle, with only sea cucumbers and empty
+/// spaces on it
+fn validate(lines: &[&str]) -> Vec<AdventError> {
+    check_grid(lines, |c| ">v.".contains(c), "'>', 'v', or '.'")
+}
+
+/// A sea floor where the herds eventually stop moving.  Not every one does,
+/// so random ones are tried until one does.
+fn generate(random: &mut Random, size: usize) -> Vec<String> {
+    loop {
+        let lines: Vec<String> = (0..size)
+            .map(|_| {
+                (0..size + 2)
+                    .map(|_| *random.choose(&['>', 'v', '.', '.']))
+                    .collect()
+            })
+            .collect();
+        let strs: Vec<&str> = lines.iter().map(|s| &s[..]).collect();
+        let grid = parse_input(&strs).unwrap();
+        if grid.run_until_stable(10 * size, one_step).is_some() {
+            return lines;
+        }
+    }
+}
+
+pub fn make_day_25() -> Day {
+    Day::new(2021, 25, DayPart::new(day_25_a), DayPart::new(day_25_b))
+        .with_validator(validate)
+        .with_generator(generate, 137)
+}
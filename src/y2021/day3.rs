@@ -0,0 +1,145 @@
+use crate::generate::Random;
+use crate::types::{AdventError, AdventResult, Answer, Day, DayPart};
+
+/// Checks that the input is binary numbers that all have the same number
+/// of bits, and returns the number of bits.
+fn check_input(lines: &[&str]) -> AdventResult<usize> {
+    let number_of_bits = match lines.first() {
+        Some(line) if !line.is_empty() => line.len(),
+        _ => return Err(Box::new(AdventError::new("no numbers in input"))),
+    };
+    if 64 < number_of_bits {
+        return Err(Box::new(AdventError::parse(1, lines[0], "too many bits")));
+    }
+    for (i, line) in lines.iter().enumerate() {
+        if line.len() != number_of_bits || !line.bytes().all(|b| b == b'0' || b == b'1') {
+            return Err(Box::new(AdventError::parse(
+                i + 1,
+                line,
+                &format!("expected {} binary digits", number_of_bits),
+            )));
+        }
+    }
+    Ok(number_of_bits)
+}
+
+#[test]
+fn test_check_input() {
+    assert_eq!(3, check_input(&["011", "110"]).unwrap());
+    assert!(check_input(&["011", "11"]).is_err());
+    assert!(check_input(&["011", "121"]).is_err());
+    assert!(check_input(&[]).is_err());
+}
+
+fn day_3_a(lines: &[&str]) -> AdventResult<Answer> {
+    let number_of_bits = check_input(lines)?;
+    let numbers: Vec<u64> = lines
+        .iter()
+        .map(|s| u64::from_str_radix(s, 2))
+        .collect::<Result<_, _>>()?;
+    let mut epsilon: u64 = 0;
+    let mut gamma: u64 = 0;
+    for i in 0..number_of_bits {
+        let mask: u64 = 1 << i;
+        let number_of_ones = numbers.iter().filter(|n| *n & mask != 0).count();
+        if number_of_ones < numbers.len() / 2 {
+            epsilon += mask;
+        } else {
+            gamma += mask;
+        }
+    }
+    Ok((epsilon * gamma).into())
+}
+
+/// Returns the most common bit in a sequence of binary numbers
+/// represented as strings of '0' and '1'.
+///
+/// TODO: switch from String to &str
+fn most_common_bit_in_column(numbers: &[&str], index: usize) -> char {
+    let number_of_ones = numbers
+        .iter()
+        .filter(|s| s.as_bytes()[index] == '1' as u8)
+        .count();
+    if numbers.len() <= number_of_ones * 2 {
+        '1'
+    } else {
+        '0'
+    }
+}
+
+#[test]
+fn test_most_common_bit_in_column() {
+    let data = ["0001", "0011", "0111", "1111"];
+    assert_eq!('0', most_common_bit_in_column(&data, 0));
+    assert_eq!('1', most_common_bit_in_column(&data, 1));
+    assert_eq!('1', most_common_bit_in_column(&data, 2));
+    assert_eq!('1', most_common_bit_in_column(&data, 3));
+}
+
+fn day_3_b_helper(lines: &[&str], index: usize, keep_common: bool) -> String {
+    if lines.len() == 0 {
+        panic!("no lines in input");
+    } else if lines.len() == 1 {
+        lines[0].to_string()
+    } else {
+        let most_common = most_common_bit_in_column(lines, index);
+        let matching: Vec<&str> = lines
+            .iter()
+            .filter(|s| (s.as_bytes()[index] == most_common as u8) == keep_common)
+            .map(|&s| s)
+            .collect();
+        day_3_b_helper(&matching, index + 1, keep_common)
+    }
+}
+fn day_3_b(lines: &[&str]) -> AdventResult<Answer> {
+    check_input(lines)?;
+    let oxygen_line = day_3_b_helper(lines, 0, true);
+    let oxygen = u64::from_str_radix(&oxygen_line, 2)?;
+    let co2_line = day_3_b_helper(lines, 0, false);
+    let co2 = u64::from_str_radix(&co2_line, 2)?;
+    Ok((oxygen * co2).into())
+}
+
+/// Does filtering the numbers the way part B does leave one of them?  It
+/// doesn't if the numbers left all have the same bit, and we're keeping the
+/// least common one.
+fn can_filter(lines: &[&str], keep_common: bool) -> bool {
+    let mut lines = lines.to_vec();
+    for index in 0..lines[0].len() {
+        if lines.len() == 1 {
+            break;
+        }
+        let most_common = most_common_bit_in_column(&lines, index);
+        lines.retain(|s| (s.as_bytes()[index] == most_common as u8) == keep_common);
+    }
+    lines.len() == 1
+}
+
+/// Different numbers that all have the same number of bits, which is at
+/// least 12, and enough to make that many different numbers.  Not every
+/// set of numbers can be filtered down to one, so random ones are tried
+/// until one can.
+fn generate(random: &mut Random, size: usize) -> Vec<String> {
+    let width = (usize::BITS - size.leading_zeros() + 1).clamp(12, 64) as usize;
+    loop {
+        let mut numbers = std::collections::HashSet::new();
+        while numbers.len() < size {
+            numbers.insert(random.next_u64() >> (64 - width));
+        }
+        let mut numbers: Vec<u64> = numbers.into_iter().collect();
+        numbers.sort();
+        random.shuffle(&mut numbers);
+        let lines: Vec<String> = numbers
+            .iter()
+            .map(|n| format!("{:0width$b}", n, width = width))
+            .collect();
+        let strs: Vec<&str> = lines.iter().map(|s| &s[..]).collect();
+        if can_filter(&strs, true) && can_filter(&strs, false) {
+            return lines;
+        }
+    }
+}
+
+pub fn make_day_3() -> Day {
+    Day::new(2021, 3, DayPart::new(day_3_a), DayPart::new(day_3_b)).with_generator(generate, 1000)
+}
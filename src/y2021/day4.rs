@@ -3,7 +3,8 @@ use std::collections::HashSet;
 use itertools::all;
 use ndarray::{s, Array2}; // TODO: fix unused warning, and keep available for tests
 
-use crate::types::{AdventResult, Answer, Day, DayPart};
+use crate::generate::Random;
+use crate::types::{at_line, parse_lines, AdventError, AdventResult, Answer, Day, DayPart};
 
 /// A number on a Day 4 bingo card
 type BingoCardNumber = u8;
@@ -64,16 +65,29 @@ fn test_is_bingo() {
     assert_eq!(true, card.is_bingo(&make_set(&[1, 3])));
 }
 
-fn parse_bingo_card(lines: &[&str]) -> BingoCard {
+/// Parses the lines of one card, which start at line `first_line` of the input
+fn parse_bingo_card(lines: &[&str], first_line: usize) -> AdventResult<BingoCard> {
     let size = lines.len();
+    let rows = parse_lines(lines, first_line, |line| {
+        let row = line
+            .split_whitespace()
+            .map(|s| s.parse())
+            .collect::<Result<Vec<BingoCardNumber>, _>>()?;
+        if row.len() != size {
+            return Err(Box::new(AdventError::new(&format!(
+                "expected {} numbers, to make a square card",
+                size
+            ))));
+        }
+        Ok(row)
+    })?;
     let mut grid = Array2::<BingoCardNumber>::zeros((size, size));
-    for (y, line) in lines.iter().enumerate() {
-        for (x, num_str) in line.split_whitespace().enumerate() {
-            let number: BingoCardNumber = num_str.parse().unwrap();
+    for (y, row) in rows.iter().enumerate() {
+        for (x, &number) in row.iter().enumerate() {
             grid[(y, x)] = number;
         }
     }
-    BingoCard { grid }
+    Ok(BingoCard { grid })
 }
 
 #[test]
@@ -82,8 +96,15 @@ fn test_parse_bingo_card() {
         BingoCard {
             grid: ndarray::arr2(&[[1, 2], [3, 4]])
         },
-        parse_bingo_card(&["1 2", " 3  4 "])
-    )
+        parse_bingo_card(&["1 2", " 3  4 "], 1).unwrap()
+    );
+    assert_eq!(
+        "AdventError: parse error: line 8: \"3 4 5\": expected 2 numbers, to make a square card",
+        parse_bingo_card(&["1 2", "3 4 5"], 7)
+            .unwrap_err()
+            .to_string()
+    );
+    assert!(parse_bingo_card(&["1 2", "3 x"], 1).is_err());
 }
 
 /// Holds the input to Day 4 problems
@@ -96,14 +117,30 @@ struct Day4Input {
     cards: Vec<BingoCard>,
 }
 
-fn parse_day_4_input(lines: &[&str]) -> Day4Input {
-    let called: Vec<BingoCardNumber> = lines[0].split(",").map(|s| s.parse().unwrap()).collect();
-    let cards: Vec<BingoCard> = lines[1..]
-        .split(|line| *line == "")
-        .filter(|lines| lines.len() != 0)
-        .map(|g| parse_bingo_card(g))
-        .collect();
-    Day4Input { called, cards }
+fn parse_day_4_input(lines: &[&str]) -> AdventResult<Day4Input> {
+    let first = lines
+        .first()
+        .ok_or_else(|| AdventError::new("no numbers to call"))?;
+    let called: Vec<BingoCardNumber> = first
+        .split(",")
+        .map(|s| s.parse())
+        .collect::<Result<_, _>>()
+        .map_err(|e| at_line(Box::new(e), 1, first))?;
+
+    // The cards are separated by blank lines
+    let mut cards = Vec::new();
+    let mut start = 1;
+    while start < lines.len() {
+        let size = lines[start..]
+            .iter()
+            .take_while(|line| !line.is_empty())
+            .count();
+        if size != 0 {
+            cards.push(parse_bingo_card(&lines[start..start + size], start + 1)?);
+        }
+        start += size + 1;
+    }
+    Ok(Day4Input { called, cards })
 }
 
 #[test]
@@ -120,26 +157,28 @@ fn test_parse_day_4_input() {
                 }
             ]
         },
-        parse_day_4_input(&["13,15", "", "1 2", "3 4", "", "5 6", "7 8"])
-    )
+        parse_day_4_input(&["13,15", "", "1 2", "3 4", "", "5 6", "7 8"]).unwrap()
+    );
+    assert!(parse_day_4_input(&["13,x", "", "1 2", "3 4"]).is_err());
+    assert!(parse_day_4_input(&["13,15", "", "1 2", "3 4", "", "5 6", "7"]).is_err());
 }
 
 fn day_4_a(lines: &[&str]) -> AdventResult<Answer> {
-    let input = parse_day_4_input(lines);
+    let input = parse_day_4_input(lines)?;
     let mut picked_so_far = HashSet::<BingoCardNumber>::new();
     for &draw in input.called.iter() {
         picked_so_far.insert(draw);
         for card in input.cards.iter() {
             if card.is_bingo(&picked_so_far) {
-                return Ok(card.score(&picked_so_far, draw));
+                return Ok(card.score(&picked_so_far, draw).into());
             }
         }
     }
-    Ok(0)
+    Ok(0u64.into())
 }
 
 fn day_4_b(lines: &[&str]) -> AdventResult<Answer> {
-    let input = parse_day_4_input(lines);
+    let input = parse_day_4_input(lines)?;
     let mut picked_so_far = HashSet::<BingoCardNumber>::new();
     // all of the cards that have won so far
     let mut winners = HashSet::<usize>::new();
@@ -150,19 +189,36 @@ fn day_4_b(lines: &[&str]) -> AdventResult<Answer> {
                 if card.is_bingo(&picked_so_far) {
                     winners.insert(i);
                     if winners.len() == input.cards.len() {
-                        return Ok(card.score(&picked_so_far, draw));
+                        return Ok(card.score(&picked_so_far, draw).into());
                     }
                 }
             }
         }
     }
-    Ok(0)
+    Ok(0u64.into())
+}
+
+/// Cards with different numbers on them, and a list of numbers to call that
+/// has all of them, so every card wins eventually
+fn generate(random: &mut Random, size: usize) -> Vec<String> {
+    let mut numbers: Vec<usize> = (0..100).collect();
+    random.shuffle(&mut numbers);
+    let mut result = vec![numbers
+        .iter()
+        .map(|n| n.to_string())
+        .collect::<Vec<String>>()
+        .join(",")];
+    for _ in 0..size {
+        random.shuffle(&mut numbers);
+        result.push("".to_string());
+        for row in numbers[..25].chunks(5) {
+            let row: Vec<String> = row.iter().map(|n| format!("{:2}", n)).collect();
+            result.push(row.join(" "));
+        }
+    }
+    result
 }
 
 pub fn make_day_4() -> Day {
-    Day::new(
-        4,
-        DayPart::new(day_4_a, 4512, 58374),
-        DayPart::new(day_4_b, 1924, 11377),
-    )
+    Day::new(2021, 4, DayPart::new(day_4_a), DayPart::new(day_4_b)).with_generator(generate, 100)
 }
@@ -1,7 +1,8 @@
 use std::collections::HashMap;
 use std::str::FromStr;
 
-use crate::types::{AdventError, AdventResult, Answer, Day, DayPart};
+use crate::generate::Random;
+use crate::types::{parse_lines, AdventError, AdventResult, Answer, Day, DayPart};
 
 #[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 struct Point {
@@ -28,13 +29,13 @@ impl FromStr for Point {
 
     fn from_str(s: &str) -> Result<Point, Self::Err> {
         let parts: Vec<String> = s.split(",").map(|s| s.to_string()).collect();
+        let parse = |part: &str| {
+            u16::from_str(part).map_err(|_| AdventError::new(&format!("bad number: {:?}", part)))
+        };
         if parts.len() != 2 {
-            Err(AdventError::new("too many commas in point"))
+            Err(AdventError::new("expected two numbers in point"))
         } else {
-            Ok(Point::new(
-                u16::from_str(&parts[0]).unwrap(),
-                u16::from_str(&parts[1]).unwrap(),
-            ))
+            Ok(Point::new(parse(&parts[0])?, parse(&parts[1])?))
         }
     }
 }
@@ -42,6 +43,8 @@ impl FromStr for Point {
 #[test]
 fn test_parse_point() {
     assert_eq!(Point::new(1, 2), Point::from_str("1,2").unwrap());
+    assert!(Point::from_str("1,2,3").is_err());
+    assert!(Point::from_str("1,-2").is_err());
 }
 
 #[derive(Debug, Eq, PartialEq)]
@@ -77,9 +80,9 @@ impl PointRange {
                 .map(|x| Point::new(x, self.p1.y))
                 .collect()
         } else {
+            // Diagonals are always at 45 degrees; from_str checks
             let x_range = range_inclusive(self.p1.x, self.p2.x);
             let y_range = range_inclusive(self.p1.y, self.p2.y);
-            // TODO: panic if ranges are not the same length
             x_range
                 .zip(y_range)
                 .map(|(x, y)| Point::new(x, y))
@@ -94,13 +97,16 @@ impl FromStr for PointRange {
     fn from_str(s: &str) -> Result<PointRange, Self::Err> {
         let parts: Vec<String> = s.split(" -> ").map(|s| s.to_string()).collect();
         if parts.len() != 2 {
-            Err(AdventError::new("bad point range"))
-        } else {
-            Ok(PointRange::new(
-                Point::from_str(&parts[0]).unwrap(),
-                Point::from_str(&parts[1]).unwrap(),
-            ))
+            return Err(AdventError::new("bad point range"));
+        }
+        let range = PointRange::new(Point::from_str(&parts[0])?, Point::from_str(&parts[1])?);
+        let (p1, p2) = (range.p1, range.p2);
+        if !range.is_horiz_or_vertical() && p1.x.abs_diff(p2.x) != p1.y.abs_diff(p2.y) {
+            return Err(AdventError::new(
+                "lines must be horizontal, vertical, or at 45 degrees",
+            ));
         }
+        Ok(range)
     }
 }
 
@@ -109,7 +115,9 @@ fn test_parse_point_range() {
     assert_eq!(
         PointRange::new(Point::new(1, 2), Point::new(3, 4)),
         PointRange::from_str("1,2 -> 3,4").unwrap()
-    )
+    );
+    assert!(PointRange::from_str("1,2 -> 3,5").is_err());
+    assert!(PointRange::from_str("1,2 - 3,4").is_err());
 }
 
 #[test]
@@ -132,10 +140,13 @@ fn test_points_in_range() {
     );
 }
 
+fn parse_input(lines: &[&str]) -> AdventResult<Vec<PointRange>> {
+    parse_lines(lines, 1, |line| Ok(PointRange::from_str(line)?))
+}
+
 fn day_5_a(lines: &[&str]) -> AdventResult<Answer> {
     let mut point_to_count: HashMap<Point, u32> = HashMap::new();
-    for line in lines.iter() {
-        let point_range = PointRange::from_str(line)?;
+    for point_range in parse_input(lines)? {
         if point_range.is_horiz_or_vertical() {
             for point in point_range.points().iter() {
                 point_to_count.insert(*point, point_to_count.get(point).unwrap_or(&0) + 1);
@@ -143,25 +154,48 @@ fn day_5_a(lines: &[&str]) -> AdventResult<Answer> {
         }
     }
     let count = point_to_count.iter().filter(|(_, &v)| 1 < v).count();
-    Ok(count as u64)
+    Ok(count.into())
 }
 
 fn day_5_b(lines: &[&str]) -> AdventResult<Answer> {
     let mut point_to_count: HashMap<Point, u32> = HashMap::new();
-    for line in lines.iter() {
-        let point_range = PointRange::from_str(line)?;
+    for point_range in parse_input(lines)? {
         for point in point_range.points().iter() {
             point_to_count.insert(*point, point_to_count.get(point).unwrap_or(&0) + 1);
         }
     }
     let count = point_to_count.iter().filter(|(_, &v)| 1 < v).count();
-    Ok(count as u64)
+    Ok(count.into())
+}
+
+/// Lines that are horizontal, vertical, or at 45 degrees
+fn generate(random: &mut Random, size: usize) -> Vec<String> {
+    let directions = [
+        (1, 0),
+        (-1, 0),
+        (0, 1),
+        (0, -1),
+        (1, 1),
+        (1, -1),
+        (-1, 1),
+        (-1, -1),
+    ];
+    // How far a line can go from a coordinate in one direction
+    let room = |n: i64, d: i64| match d {
+        1 => 999 - n,
+        -1 => n,
+        _ => 999,
+    };
+    (0..size)
+        .map(|_| {
+            let (x1, y1) = (random.range(0..=999), random.range(0..=999));
+            let &(dx, dy) = random.choose(&directions);
+            let length = random.range(0..=room(x1, dx).min(room(y1, dy)));
+            format!("{},{} -> {},{}", x1, y1, x1 + dx * length, y1 + dy * length)
+        })
+        .collect()
 }
 
 pub fn make_day_5() -> Day {
-    Day::new(
-        5,
-        DayPart::new(day_5_a, 5, 6311),
-        DayPart::new(day_5_b, 12, 19929),
-    )
+    Day::new(2021, 5, DayPart::new(day_5_a), DayPart::new(day_5_b)).with_generator(generate, 500)
 }
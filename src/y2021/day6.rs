@@ -0,0 +1,115 @@
+use std::io::BufRead;
+
+use crate::generate::Random;
+use crate::types::{AdventError, AdventResult, Answer, Day, DayPart};
+
+/// Counter for the number of fish at a given age (countdown number)
+type FishCount = u64;
+
+/// The state, holding the number of fish for each count-down value.
+type State = [FishCount; 9];
+
+/// Reads the one line of comma-separated countdowns, a number at a time, so
+/// that it doesn't matter how long the line is.
+fn parse_input(input: &mut dyn BufRead) -> AdventResult<State> {
+    let mut state: State = [0; 9];
+    for item in input.split(b',') {
+        let item = item?;
+        let text = std::str::from_utf8(&item)?;
+        if text.trim_end().contains('\n') {
+            return Err(Box::new(AdventError::new(
+                "expected exactly one input line",
+            )));
+        }
+        let c: usize = text.trim_end().parse()?;
+        if state.len() <= c {
+            return Err(Box::new(AdventError::new(&format!(
+                "countdown out of range: {}",
+                c
+            ))));
+        }
+        state[c] += 1;
+    }
+    Ok(state)
+}
+
+#[test]
+fn test_parse_input() {
+    assert_eq!(
+        [0, 1, 1, 2, 1, 0, 0, 0, 0],
+        parse_input(&mut "3,4,3,1,2\n".as_bytes()).unwrap()
+    );
+    assert!(parse_input(&mut "3,4\n3,1\n".as_bytes()).is_err());
+    assert!(parse_input(&mut "3,9".as_bytes()).is_err());
+}
+
+// Given a starting state, what's the state on the next day?
+fn next_state(state: &State) -> State {
+    [
+        state[1],
+        state[2],
+        state[3],
+        state[4],
+        state[5],
+        state[6],
+        state[7] + state[0],
+        state[8],
+        state[0],
+    ]
+}
+
+fn run_n_days(input: &mut dyn BufRead, day_count: usize) -> AdventResult<Answer> {
+    let mut state = parse_input(input)?;
+    for _ in 0..day_count {
+        state = next_state(&state);
+    }
+    let sum: u64 = state.iter().sum();
+    Ok(sum.into())
+}
+
+fn day_6_a(input: &mut dyn BufRead) -> AdventResult<Answer> {
+    run_n_days(input, 80)
+}
+
+fn day_6_b(input: &mut dyn BufRead) -> AdventResult<Answer> {
+    run_n_days(input, 256)
+}
+
+/// Follows every fish, one at a time, instead of counting how many have
+/// each countdown
+fn reference_a(lines: &[&str]) -> AdventResult<Answer> {
+    let mut fish: Vec<u8> = lines
+        .join(",")
+        .split(',')
+        .map(|s| s.parse())
+        .collect::<Result<_, _>>()?;
+    for _ in 0..80 {
+        let mut born = 0;
+        for countdown in fish.iter_mut() {
+            if *countdown == 0 {
+                *countdown = 6;
+                born += 1;
+            } else {
+                *countdown -= 1;
+            }
+        }
+        fish.resize(fish.len() + born, 8);
+    }
+    Ok(fish.len().into())
+}
+
+fn generate(random: &mut Random, size: usize) -> Vec<String> {
+    let ages: Vec<String> = (0..size).map(|_| random.range(1..=5).to_string()).collect();
+    vec![ages.join(",")]
+}
+
+pub fn make_day_6() -> Day {
+    Day::new(
+        2021,
+        6,
+        DayPart::streaming(day_6_a),
+        DayPart::streaming(day_6_b),
+    )
+    .with_generator(generate, 300)
+    .with_reference(Some(reference_a), None, 5)
+}
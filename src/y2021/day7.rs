@@ -0,0 +1,127 @@
+use num::abs;
+
+use crate::generate::Random;
+use crate::types::{at_line, AdventError, AdventResult, Answer, Day, DayPart};
+
+/// Parses the one line of comma-separated crab positions
+fn parse_positions(lines: &[&str]) -> AdventResult<Vec<i32>> {
+    if lines.len() != 1 {
+        return Err(Box::new(AdventError::new(
+            "expected exactly one input line",
+        )));
+    }
+    lines[0]
+        .split(",")
+        .map(|s| s.parse())
+        .collect::<Result<_, _>>()
+        .map_err(|e| at_line(Box::new(e), 1, lines[0]))
+}
+
+#[test]
+fn test_parse_positions() {
+    assert_eq!(vec![16, 1, 2], parse_positions(&["16,1,2"]).unwrap());
+    assert!(parse_positions(&["16,1,"]).is_err());
+    assert!(parse_positions(&["16", "1"]).is_err());
+}
+
+/// Finds the position with the least total cost for moving crabs.
+///
+/// The cost function is the sum of the distances that individual
+/// crabs move.
+///
+/// The best place is the median.  If there's a tie because of an even number
+/// of inputs, either answer (or anything in between them) will have the
+/// same total cost.  (Proof left to reader. :-) )
+///
+fn day_7_a(lines: &[&str]) -> AdventResult<Answer> {
+    let mut positions = parse_positions(lines)?;
+    positions.sort();
+    let median = positions[positions.len() / 2];
+    let total_cost: i32 = positions.iter().map(|&p| abs(p - median)).sum();
+    Ok((total_cost as u64).into())
+}
+
+/// Cost function for part B for one crab.
+///
+fn part_b_one_cost(chosen: i32, pos: i32) -> i32 {
+    let d = abs(chosen - pos);
+    (d + d * d) / 2
+}
+
+/// Cost of moving all the crabs in part B.
+fn part_b_total_cost(positions: &Vec<i32>, chosen: i32) -> i32 {
+    positions.iter().map(|&p| part_b_one_cost(chosen, p)).sum()
+}
+/// Same as day_7_a, but the cost function is (n + n^2) / 2
+///
+/// I don't know how to derive the answer mathematically, so we'll
+/// just try all the possibilities until we find the answer.
+///
+fn day_7_b(lines: &[&str]) -> AdventResult<Answer> {
+    let positions = parse_positions(lines)?;
+    let min: i32 = *(positions.iter().min().unwrap());
+    let max: i32 = *(positions.iter().max().unwrap());
+    let mut prev_cost = part_b_total_cost(&positions, min);
+    // println!("{:?} - {:?}", min, prev_cost);
+    for p in (min + 1)..=max {
+        let this_cost = part_b_total_cost(&positions, p);
+        // println!("{:?} - {:?}", p, this_cost);
+        if this_cost <= prev_cost {
+            prev_cost = this_cost;
+        } else {
+            break;
+        }
+    }
+    // If the cost never went back up, the cheapest place is the last one
+    Ok((prev_cost as u64).into())
+}
+
+#[test]
+fn test_day_7_b() {
+    assert_eq!(
+        Answer::from(168u64),
+        day_7_b(&["16,1,2,0,4,2,7,1,2,14"]).unwrap()
+    );
+    // All of the crabs are already in the same place
+    assert_eq!(Answer::from(0u64), day_7_b(&["5,5"]).unwrap());
+}
+
+/// Tries every place the crabs could meet, instead of knowing where the
+/// best one is
+fn cheapest_meeting(lines: &[&str], cost: fn(i64) -> i64) -> AdventResult<Answer> {
+    let positions = parse_positions(lines)?;
+    let min = *positions.iter().min().unwrap();
+    let max = *positions.iter().max().unwrap();
+    let total_cost = (min..=max)
+        .map(|chosen| {
+            positions
+                .iter()
+                .map(|&p| cost(abs(chosen - p) as i64))
+                .sum::<i64>()
+        })
+        .min()
+        .unwrap();
+    Ok((total_cost as u64).into())
+}
+
+fn reference_a(lines: &[&str]) -> AdventResult<Answer> {
+    cheapest_meeting(lines, |distance| distance)
+}
+
+fn reference_b(lines: &[&str]) -> AdventResult<Answer> {
+    cheapest_meeting(lines, |distance| (1..=distance).sum())
+}
+
+/// Crabs that are mostly near the start
+fn generate(random: &mut Random, size: usize) -> Vec<String> {
+    let positions: Vec<String> = (0..size)
+        .map(|_| (random.range(0..=1999) * random.range(0..=1999) / 2000).to_string())
+        .collect();
+    vec![positions.join(",")]
+}
+
+pub fn make_day_7() -> Day {
+    Day::new(2021, 7, DayPart::new(day_7_a), DayPart::new(day_7_b))
+        .with_generator(generate, 1000)
+        .with_reference(Some(reference_a), Some(reference_b), 10)
+}
@@ -0,0 +1,420 @@
+use std::fmt;
+use std::ops::BitAnd;
+use std::str::FromStr;
+
+use crate::generate::Random;
+use crate::types::{parse_lines, AdventError, AdventResult, Answer, Day, DayPart};
+
+/// A displayed digit, with some subset of the seven segments lit up.
+///
+/// We don't know which segment is which.
+///
+#[derive(Clone, Copy, PartialEq)]
+struct Display {
+    // Segment n is lit up if bit (1 << n) is on
+    bits: u8,
+
+    // Number of segments lit up
+    count: u8,
+}
+
+impl Display {
+    fn new(bits: u8) -> Display {
+        let count = (0..=7).filter(|n| bits & (1u8 << n) != 0).count() as u8;
+        Display { bits, count }
+    }
+
+    fn contains(self, other: &Display) -> bool {
+        (self.bits & other.bits) == other.bits
+    }
+}
+
+#[test]
+fn test_contains() {
+    assert_eq!(true, Display::new(7).contains(&Display::new(3)));
+    assert_eq!(false, Display::new(7).contains(&Display::new(12)));
+}
+
+impl fmt::Debug for Display {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for i in 0..7 {
+            if self.bits & (1 << i) != 0 {
+                write!(f, "{}", (b'a' + i) as char)?
+            }
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_debug_display() {
+    assert_eq!("ace", format!("{:?}", Display::new(21)))
+}
+
+impl BitAnd<Display> for Display {
+    type Output = Display;
+
+    fn bitand(self, rhs: Display) -> Display {
+        Display::new(self.bits & rhs.bits)
+    }
+}
+
+#[test]
+fn test_bitand_display() {
+    assert_eq!(Display::new(5), Display::new(7) & Display::new(13))
+}
+
+impl FromStr for Display {
+    type Err = AdventError;
+
+    fn from_str(s: &str) -> Result<Display, Self::Err> {
+        let mut bits = 0;
+        for &c in s.as_bytes() {
+            if c < b'a' || b'g' < c {
+                return Err(AdventError::new(&format!(
+                    "Illegal character: {:?}",
+                    c as char
+                )));
+            }
+            bits |= 1 << (c - b'a');
+        }
+        Ok(Display::new(bits))
+    }
+}
+
+#[test]
+fn test_parse_display() {
+    assert_eq!(
+        Display { bits: 5, count: 2 },
+        Display::from_str("ca").unwrap()
+    );
+}
+
+/// Parses a list of digits separated by spaces
+fn parse_display_list(s: &str) -> Result<Vec<Display>, AdventError> {
+    s.split_whitespace().map(Display::from_str).collect()
+}
+
+/// Input line with ten sample digits, and the four digits of output
+///
+#[derive(Debug, PartialEq)]
+struct InputLine {
+    // The 10 sample displays on the left of the separator
+    samples: Vec<Display>,
+
+    // The four digits displayed after the separator
+    output: Vec<Display>,
+}
+
+impl FromStr for InputLine {
+    type Err = AdventError;
+
+    fn from_str(s: &str) -> Result<InputLine, Self::Err> {
+        let (samples, output) = s
+            .split_once("|")
+            .ok_or_else(|| AdventError::new("expected samples | output"))?;
+        let samples = parse_display_list(samples)?;
+        let output = parse_display_list(output)?;
+        Ok(InputLine { samples, output })
+    }
+}
+
+#[test]
+fn test_parse_input_line() {
+    assert_eq!(
+        InputLine {
+            samples: vec![
+                Display::from_str("acedgfb").unwrap(),
+                Display::from_str("cdfbe").unwrap()
+            ],
+            output: vec![Display::from_str("cdfeb").unwrap()]
+        },
+        InputLine::from_str("acedgfb cdfbe | cdfeb").unwrap()
+    );
+    assert!(InputLine::from_str("acedgfb cdfbe cdfeb").is_err());
+    assert!(InputLine::from_str("acedgfb cdfbx | cdfeb").is_err());
+}
+
+fn parse_input(lines: &[&str]) -> AdventResult<Vec<InputLine>> {
+    parse_lines(lines, 1, |line| Ok(InputLine::from_str(line)?))
+}
+
+/// Maps from the count of lit LEDs to the digit, if the
+/// LED count is sufficient info to know.
+fn count_to_digit(n: u8) -> Option<usize> {
+    match n {
+        2 => Some(1),
+        3 => Some(7),
+        4 => Some(4),
+        7 => Some(8),
+        _ => None,
+    }
+}
+
+/// Maps from the count of lit LEDs to the digit, if the
+/// LED count is sufficient info to know.
+fn sample_and_mapping_to_digit(sample: Display, mapping: &[Display; 10]) -> Option<usize> {
+    match sample.count {
+        5 => {
+            if sample.contains(&mapping[1]) {
+                Some(3)
+            } else {
+                let share_with_4 = (sample & mapping[4]).count;
+                if share_with_4 == 3 {
+                    Some(5)
+                } else {
+                    Some(2)
+                }
+            }
+        }
+        6 => {
+            if sample.contains(&mapping[4]) {
+                Some(9)
+            } else if !sample.contains(&mapping[1]) {
+                Some(6)
+            } else {
+                Some(0)
+            }
+        }
+        _ => None,
+    }
+}
+
+/// Figures out the digit mapping on one line, and translates the output.
+/// Patterns that contradict each other may leave an output that isn't any
+/// digit, and then there's no answer.
+fn solve_one_line(input: &InputLine) -> Option<Vec<u8>> {
+    // For each digit which of the samples is used to represent it
+    let mut mapping: [Display; 10] = [Display::new(0); 10];
+
+    // First, assign the mappings to the digits that have unique
+    // counts of lit LEDs.
+    for &sample in input.samples.iter() {
+        match count_to_digit(sample.count) {
+            Some(digit) => mapping[digit] = sample,
+            _ => {}
+        }
+    }
+
+    // Now we can assign the rest, based on the mappings for 2, 3, 4, and 7.
+    for &sample in input.samples.iter() {
+        match sample_and_mapping_to_digit(sample, &mapping) {
+            Some(digit) => mapping[digit] = sample,
+            _ => {}
+        }
+    }
+
+    // Function to map from an output display to a digit
+    fn output_to_digit(output: Display, mapping: &[Display; 10]) -> Option<u8> {
+        for i in 0..10 {
+            if mapping[i] == output {
+                return Some(i as u8);
+            }
+        }
+        None
+    }
+
+    input
+        .output
+        .iter()
+        .map(|&out| output_to_digit(out, &mapping))
+        .collect()
+}
+
+#[test]
+fn test_solve_one_line() {
+    assert_eq! {
+        Some(vec![5, 3, 5, 3]),
+        solve_one_line(&InputLine::from_str("acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf").unwrap())
+    }
+}
+
+/// The output digits on each line
+fn solve_lines(lines: &[&str]) -> AdventResult<Vec<Vec<u8>>> {
+    let inputs = parse_input(lines)?;
+    let mut result = Vec::new();
+    for (i, (input, line)) in inputs.iter().zip(lines).enumerate() {
+        match solve_one_line(input) {
+            Some(digits) => result.push(digits),
+            None => {
+                return Err(Box::new(
+                    AdventError::no_solution("an output isn't any of the digits")
+                        .on_line(i + 1, line),
+                ))
+            }
+        }
+    }
+    Ok(result)
+}
+
+#[test]
+fn test_solve_lines() {
+    use crate::types::ErrorKind;
+
+    let good =
+        "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf";
+    assert_eq!(vec![vec![5, 3, 5, 3]], solve_lines(&[good]).unwrap());
+
+    // The patterns are fine, but the output isn't one of them
+    let bad = "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb g";
+    let error = solve_lines(&[good, bad])
+        .unwrap_err()
+        .downcast::<AdventError>()
+        .unwrap();
+    assert_eq!(ErrorKind::NoSolution, error.kind());
+    assert_eq!(2, error.location().unwrap().line);
+    assert!(error
+        .to_string()
+        .ends_with("an output isn't any of the digits"));
+}
+
+fn day_8_a(lines: &[&str]) -> AdventResult<Answer> {
+    let count: usize = solve_lines(lines)?
+        .into_iter()
+        .flatten()
+        .filter(|&n| n == 1 || n == 4 || n == 7 || n == 8)
+        .count();
+    Ok(count.into())
+}
+
+/// Converts a vector of base 10 digits into a number.
+///
+/// [1, 2 ,3] becomes 123
+///
+fn vector_to_number(digits: &Vec<u8>) -> u64 {
+    let mut result = 0;
+    for &d in digits {
+        result = result * 10 + (d as u64);
+    }
+    result
+}
+
+#[test]
+fn test_vector_to_number() {
+    assert_eq!(1234, vector_to_number(&vec![1, 2, 3, 4]));
+}
+
+fn day_8_b(lines: &[&str]) -> AdventResult<Answer> {
+    let total: u64 = solve_lines(lines)?.iter().map(vector_to_number).sum();
+    Ok(total.into())
+}
+
+/// The number of segments lit up by each of the ten digits, in order
+const SEGMENT_COUNTS: [u8; 10] = [2, 3, 4, 5, 5, 5, 6, 6, 6, 7];
+
+/// What's wrong with one line of input, if anything.  The solver assumes
+/// that each line has ten different patterns, one for each digit, and that
+/// each of the four outputs is one of them.
+fn check_input_line(input: &InputLine) -> Vec<String> {
+    let mut result = Vec::new();
+    if input.samples.len() != 10 {
+        result.push(format!(
+            "expected ten patterns, found {}",
+            input.samples.len()
+        ));
+    }
+    if input.output.len() != 4 {
+        result.push(format!(
+            "expected four output digits, found {}",
+            input.output.len()
+        ));
+    }
+    let mut counts: Vec<u8> = input.samples.iter().map(|d| d.count).collect();
+    counts.sort();
+    if input.samples.len() == 10 && counts != SEGMENT_COUNTS {
+        result.push(format!(
+            "the patterns light up {:?} segments, not {:?}",
+            counts, SEGMENT_COUNTS
+        ));
+    }
+    for (i, sample) in input.samples.iter().enumerate() {
+        if input.samples[..i].contains(sample) {
+            result.push(format!("pattern {:?} is there twice", sample));
+        }
+    }
+    for output in input.output.iter() {
+        if !input.samples.contains(output) {
+            result.push(format!("output {:?} isn't one of the patterns", output));
+        }
+    }
+    result
+}
+
+fn validate(lines: &[&str]) -> Vec<AdventError> {
+    let mut result = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        match InputLine::from_str(line) {
+            Ok(input) => {
+                for problem in check_input_line(&input) {
+                    result.push(AdventError::parse(i + 1, line, &problem));
+                }
+            }
+            Err(e) => result.push(AdventError::parse(i + 1, line, &e.to_string())),
+        }
+    }
+    result
+}
+
+#[test]
+fn test_validate() {
+    let good =
+        "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf";
+    assert!(validate(&[good, good]).is_empty());
+
+    let problems = validate(&[
+        good,
+        "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb ab | cdfeb fcadb cdfeb",
+        "acedgfb | x",
+    ]);
+    let messages: Vec<(usize, String)> = problems
+        .iter()
+        .map(|e| (e.location().unwrap().line, e.to_string()))
+        .collect();
+    assert_eq!(3, messages.len());
+    assert_eq!(2, messages[0].0);
+    assert!(messages[0].1.ends_with("expected ten patterns, found 9"));
+    assert!(messages[1]
+        .1
+        .ends_with("expected four output digits, found 3"));
+    assert_eq!(3, messages[2].0);
+}
+
+/// The segments lit up for each digit, when the wires aren't mixed up
+const DIGIT_SEGMENTS: [&str; 10] = [
+    "abcefg", "cf", "acdeg", "acdfg", "bcdf", "abdfg", "abdefg", "acf", "abcdefg", "abcdfg",
+];
+
+/// Each line has the wires mixed up a different way, and the letters in
+/// each pattern in a random order
+fn generate(random: &mut Random, size: usize) -> Vec<String> {
+    (0..size)
+        .map(|_| {
+            let mut wiring: Vec<char> = "abcdefg".chars().collect();
+            random.shuffle(&mut wiring);
+            let pattern = |random: &mut Random, digit: usize| {
+                let mut letters: Vec<char> = DIGIT_SEGMENTS[digit]
+                    .bytes()
+                    .map(|b| wiring[(b - b'a') as usize])
+                    .collect();
+                random.shuffle(&mut letters);
+                letters.into_iter().collect::<String>()
+            };
+            let mut digits: Vec<usize> = (0..10).collect();
+            random.shuffle(&mut digits);
+            let samples: Vec<String> = digits.iter().map(|&d| pattern(random, d)).collect();
+            let output: Vec<String> = (0..4)
+                .map(|_| {
+                    let digit = random.below(10);
+                    pattern(random, digit)
+                })
+                .collect();
+            format!("{} | {}", samples.join(" "), output.join(" "))
+        })
+        .collect()
+}
+
+pub fn make_day_8() -> Day {
+    Day::new(2021, 8, DayPart::new(day_8_a), DayPart::new(day_8_b))
+        .with_validator(validate)
+        .with_generator(generate, 200)
+}
@@ -1,5 +1,6 @@
 use std::collections::HashMap;
 
+use crate::generate::Random;
 use crate::grid::{parse_grid, Grid, Point};
 use crate::types::{AdventResult, Answer, Day, DayPart};
 
@@ -14,7 +15,7 @@ fn is_low_spot(grid: &Grid, pos: Point) -> bool {
 
 #[test]
 fn test_is_low_spot() {
-    let grid = parse_grid(&["123", "303", "321"]);
+    let grid = parse_grid(&["123", "303", "321"]).unwrap();
     for x in 0..3 {
         for y in 0..3 {
             assert_eq!(x == y, is_low_spot(&grid, (x, y)));
@@ -23,7 +24,7 @@ fn test_is_low_spot() {
 }
 
 fn day_9_a(lines: &[&str]) -> AdventResult<Answer> {
-    let grid = parse_grid(lines);
+    let grid = parse_grid(lines)?;
     let (columns, rows) = grid.shape();
     let mut score = 0;
     for y in 0..rows {
@@ -33,7 +34,7 @@ fn day_9_a(lines: &[&str]) -> AdventResult<Answer> {
             }
         }
     }
-    Ok(score)
+    Ok(score.into())
 }
 
 /// Given a point, keeps going down to find the low point in
@@ -61,7 +62,7 @@ fn find_basin(grid: &Grid, point: Point) -> Option<Point> {
 
 #[test]
 fn test_find_basin() {
-    let grid = parse_grid(&vec!["123", "994", "129"]);
+    let grid = parse_grid(&vec!["123", "994", "129"]).unwrap();
     println!("{:?}", grid);
     assert_eq!(Some((0, 0)), find_basin(&grid, (0, 0)));
     assert_eq!(Some((0, 0)), find_basin(&grid, (1, 0)));
@@ -72,7 +73,7 @@ fn test_find_basin() {
 }
 
 fn day_9_b(lines: &[&str]) -> AdventResult<Answer> {
-    let grid = parse_grid(lines);
+    let grid = parse_grid(lines)?;
     let (width, height) = grid.shape();
     let mut basin_to_count: HashMap<Point, usize> = HashMap::new();
     for x in 0..width {
@@ -83,15 +84,53 @@ fn day_9_b(lines: &[&str]) -> AdventResult<Answer> {
             }
         }
     }
-    let mut counts: Vec<Answer> = basin_to_count.values().map(|&n| n as Answer).collect();
+    let mut counts: Vec<usize> = basin_to_count.values().copied().collect();
     counts.sort();
-    Ok(counts.iter().rev().take(3).product())
+    Ok(counts.iter().rev().take(3).product::<usize>().into())
+}
+
+/// A square heightmap of basins around random low points.  The basins are
+/// separated by 9s, and get higher further from their low point.
+fn generate(random: &mut Random, size: usize) -> Vec<String> {
+    let low_points: Vec<(i64, i64, i64)> = (0..(size * size / 40).max(1))
+        .map(|_| {
+            let x = random.below(size) as i64;
+            let y = random.below(size) as i64;
+            (x, y, random.range(0..=3))
+        })
+        .collect();
+    // Which low point each cell is nearest, and how far away it is
+    let nearest = |x: i64, y: i64| {
+        (0..low_points.len())
+            .map(|i| {
+                let (lx, ly, _) = low_points[i];
+                ((x - lx).abs() + (y - ly).abs(), i)
+            })
+            .min()
+            .unwrap()
+    };
+    let size = size as i64;
+    (0..size)
+        .map(|y| {
+            (0..size)
+                .map(|x| {
+                    let (distance, basin) = nearest(x, y);
+                    let on_edge = [(x - 1, y), (x + 1, y), (x, y - 1), (x, y + 1)]
+                        .iter()
+                        .filter(|(nx, ny)| (0..size).contains(nx) && (0..size).contains(ny))
+                        .any(|&(nx, ny)| nearest(nx, ny).1 != basin);
+                    if on_edge {
+                        '9'
+                    } else {
+                        let height = (low_points[basin].2 + distance).min(8);
+                        (b'0' + height as u8) as char
+                    }
+                })
+                .collect()
+        })
+        .collect()
 }
 
 pub fn make_day_9() -> Day {
-    Day::new(
-        9,
-        DayPart::new(day_9_a, 15, 506),
-        DayPart::new(day_9_b, 1134, 931200),
-    )
+    Day::new(2021, 9, DayPart::new(day_9_a), DayPart::new(day_9_b)).with_generator(generate, 100)
 }
@@ -0,0 +1,61 @@
+//! The puzzles from 2021
+
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day2;
+pub mod day20;
+pub mod day21;
+pub mod day22;
+pub mod day23;
+pub mod day24;
+pub mod day24_alu;
+pub mod day25;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+
+use crate::types::Day;
+
+/// All the days of 2021, in order
+pub fn days() -> Vec<Day> {
+    vec![
+        day1::make_day_1(),
+        day2::make_day_2(),
+        day3::make_day_3(),
+        day4::make_day_4(),
+        day5::make_day_5(),
+        day6::make_day_6(),
+        day7::make_day_7(),
+        day8::make_day_8(),
+        day9::make_day_9(),
+        day10::make_day_10(),
+        day11::make_day_11(),
+        day12::make_day_12(),
+        day13::make_day_13(),
+        day14::make_day_14(),
+        day15::make_day_15(),
+        day16::make_day_16(),
+        day17::make_day_17(),
+        day18::make_day_18(),
+        day19::make_day_19(),
+        day20::make_day_20(),
+        day21::make_day_21(),
+        day22::make_day_22(),
+        day23::make_day_23(),
+        day24::make_day_24(),
+        day25::make_day_25(),
+    ]
+}